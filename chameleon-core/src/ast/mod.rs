@@ -9,8 +9,14 @@ pub struct Schema {
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
 pub struct Entity {
     pub name: String,
+    /// Schema/namespace qualifier for multi-schema deployments, e.g. the
+    /// `analytics` in `analytics.Order`. `None` means the default schema.
+    pub namespace: Option<String>,
     pub fields: HashMap<String, Field>,
     pub relations: HashMap<String, Relation>,
+    /// Parsed from `@temporal`: system-version this entity with a
+    /// `valid_from`/`valid_to` range and a companion history table.
+    pub temporal: bool,
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -24,7 +30,7 @@ pub struct Field {
     pub backend: Option<BackendAnnotation>,
 }
 
-#[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
+#[derive(Debug, Clone, PartialEq, Eq, Hash, Serialize, Deserialize)]
 pub enum FieldType {
     UUID,
     String,
@@ -83,6 +89,7 @@ pub enum BackendAnnotation {
     OLAP,                           // @olap
     Vector,                         // @vector
     ML,                             // @ml (futuro)
+    History,                        // @history
 }
 
 #[derive(Debug, Clone, PartialEq, Serialize, Deserialize)]
@@ -113,22 +120,398 @@ impl Schema {
     pub fn get_entity_mut(&mut self, name: &str) -> Option<&mut Entity> {
         self.entities.iter_mut().find(|e| e.name == name)
     }
+
+    /// Render the schema back into canonical `.cham` source text, such that
+    /// `parse_schema(schema.to_source())` reconstructs an equal `Schema`.
+    pub fn to_source(&self) -> String {
+        self.entities.iter()
+            .map(|entity| entity.to_string())
+            .collect::<Vec<String>>()
+            .join("\n\n")
+    }
 }
 
 impl Entity {
     pub fn new(name: String) -> Self {
         Entity {
             name,
+            namespace: None,
+            fields: HashMap::new(),
+            relations: HashMap::new(),
+            temporal: false,
+        }
+    }
+
+    /// Build an entity from a possibly dotted name, e.g. `"analytics.Order"`.
+    pub fn from_qualified_name(raw: &str) -> Self {
+        let (namespace, name) = parse_qualified_name(raw);
+        Entity {
+            name,
+            namespace,
             fields: HashMap::new(),
             relations: HashMap::new(),
+            temporal: false,
         }
     }
-    
+
+    /// The entity's name as written in a relation target or DDL reference,
+    /// e.g. `"analytics.Order"` or just `"Order"` with no namespace.
+    pub fn qualified_name(&self) -> String {
+        match &self.namespace {
+            Some(ns) => format!("{}.{}", ns, self.name),
+            None => self.name.clone(),
+        }
+    }
+
     pub fn add_field(&mut self, field: Field) {
         self.fields.insert(field.name.clone(), field);
     }
-    
+
     pub fn add_relation(&mut self, relation: Relation) {
         self.relations.insert(relation.name.clone(), relation);
     }
+
+    /// Whether this entity gets a validity range, a `_history` table and
+    /// versioning triggers: either the whole entity was declared
+    /// `@temporal`, or at least one of its fields is annotated `@history`.
+    pub fn is_bitemporal(&self) -> bool {
+        self.temporal || self.fields.values().any(|f| f.backend == Some(BackendAnnotation::History))
+    }
+}
+
+impl std::fmt::Display for FieldType {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            FieldType::UUID => write!(f, "uuid"),
+            FieldType::String => write!(f, "string"),
+            FieldType::Int => write!(f, "int"),
+            FieldType::Decimal => write!(f, "decimal"),
+            FieldType::Bool => write!(f, "bool"),
+            FieldType::Timestamp => write!(f, "timestamp"),
+            FieldType::Float => write!(f, "float"),
+            FieldType::Vector(dim) => write!(f, "vector({})", dim),
+            FieldType::Array(inner) => write!(f, "[{}]", inner),
+        }
+    }
+}
+
+impl std::fmt::Display for DefaultValue {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self {
+            DefaultValue::Now => write!(f, "now()"),
+            DefaultValue::UUIDv4 => write!(f, "uuid()"),
+            DefaultValue::Literal(value) => write!(f, "\"{}\"", value),
+        }
+    }
+}
+
+impl BackendAnnotation {
+    /// The `@`-prefixed annotation as written in source, or `None` for
+    /// `OLTP` since it's the implicit default and never printed.
+    fn as_source(&self) -> Option<&'static str> {
+        match self {
+            BackendAnnotation::OLTP => None,
+            BackendAnnotation::Cache => Some("@cache"),
+            BackendAnnotation::OLAP => Some("@olap"),
+            BackendAnnotation::Vector => Some("@vector"),
+            BackendAnnotation::ML => Some("@ml"),
+            BackendAnnotation::History => Some("@history"),
+        }
+    }
+}
+
+impl std::fmt::Display for Field {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "{}: {}", self.name, self.field_type)?;
+
+        if self.primary_key {
+            write!(f, " primary")?;
+        }
+        if self.unique {
+            write!(f, " unique")?;
+        }
+        if self.nullable {
+            write!(f, " nullable")?;
+        }
+        if let Some(default) = &self.default {
+            write!(f, " default({})", default)?;
+        }
+        if let Some(backend) = &self.backend {
+            if let Some(annotation) = backend.as_source() {
+                write!(f, " {}", annotation)?;
+            }
+        }
+
+        Ok(())
+    }
+}
+
+impl std::fmt::Display for Relation {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        match self.kind {
+            RelationKind::HasMany => {
+                write!(f, "{}: [{}]", self.name, self.target_entity)?;
+                if let Some(through) = &self.through {
+                    write!(f, " through {}", through)?;
+                } else if let Some(fk) = &self.foreign_key {
+                    write!(f, " via {}", fk)?;
+                }
+                Ok(())
+            }
+            RelationKind::ManyToMany => {
+                write!(f, "{}: [{}]", self.name, self.target_entity)?;
+                if let Some(through) = &self.through {
+                    write!(f, " through {}", through)?;
+                }
+                Ok(())
+            }
+            RelationKind::HasOne | RelationKind::BelongsTo => {
+                write!(f, "{}: {}", self.name, self.target_entity)
+            }
+        }
+    }
+}
+
+impl std::fmt::Display for Entity {
+    fn fmt(&self, f: &mut std::fmt::Formatter<'_>) -> std::fmt::Result {
+        write!(f, "entity {}", self.qualified_name())?;
+        if self.temporal {
+            write!(f, " @temporal")?;
+        }
+        writeln!(f, " {{")?;
+
+        let mut field_names: Vec<&String> = self.fields.keys().collect();
+        field_names.sort();
+        for name in field_names {
+            writeln!(f, "    {},", self.fields[name])?;
+        }
+
+        let mut relation_names: Vec<&String> = self.relations.keys().collect();
+        relation_names.sort();
+        for name in relation_names {
+            writeln!(f, "    {},", self.relations[name])?;
+        }
+
+        write!(f, "}}")
+    }
+}
+
+/// Split a possibly dotted entity reference into its namespace and bare
+/// name, e.g. `"analytics.Order"` → `(Some("analytics"), "Order")`. A name
+/// with no dot has no namespace.
+pub fn parse_qualified_name(raw: &str) -> (Option<String>, String) {
+    match raw.rsplit_once('.') {
+        Some((namespace, name)) => (Some(namespace.to_string()), name.to_string()),
+        None => (None, raw.to_string()),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_field_to_source_renders_modifiers_in_order() {
+        let field = Field {
+            name: "email".to_string(),
+            field_type: FieldType::String,
+            nullable: false,
+            unique: true,
+            primary_key: false,
+            default: None,
+            backend: None,
+        };
+        assert_eq!(field.to_string(), "email: string unique");
+    }
+
+    #[test]
+    fn test_field_to_source_renders_default_and_annotation() {
+        let field = Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false,
+            unique: false,
+            primary_key: true,
+            default: Some(DefaultValue::UUIDv4),
+            backend: Some(BackendAnnotation::Cache),
+        };
+        assert_eq!(field.to_string(), "id: uuid primary default(uuid()) @cache");
+    }
+
+    #[test]
+    fn test_field_type_to_source_vector_and_array() {
+        assert_eq!(FieldType::Vector(384).to_string(), "vector(384)");
+        assert_eq!(FieldType::Array(Box::new(FieldType::Int)).to_string(), "[int]");
+    }
+
+    #[test]
+    fn test_entity_to_source_renders_namespace_and_temporal() {
+        let mut entity = Entity::from_qualified_name("analytics.Order");
+        entity.temporal = true;
+        entity.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+
+        let source = entity.to_string();
+        assert!(source.starts_with("entity analytics.Order @temporal {"));
+        assert!(source.contains("    id: uuid primary,"));
+        assert!(source.ends_with("}"));
+    }
+
+    #[test]
+    fn test_relation_to_source_has_many_via() {
+        let relation = Relation {
+            name: "orders".to_string(),
+            kind: RelationKind::HasMany,
+            target_entity: "Order".to_string(),
+            foreign_key: Some("user_id".to_string()),
+            through: None,
+        };
+        assert_eq!(relation.to_string(), "orders: [Order] via user_id");
+    }
+
+    #[test]
+    fn test_relation_to_source_many_to_many_through() {
+        let relation = Relation {
+            name: "roles".to_string(),
+            kind: RelationKind::ManyToMany,
+            target_entity: "Role".to_string(),
+            foreign_key: None,
+            through: Some("user_roles".to_string()),
+        };
+        assert_eq!(relation.to_string(), "roles: [Role] through user_roles");
+    }
+
+    #[test]
+    fn test_schema_to_source_joins_entities_with_blank_line() {
+        let mut schema = Schema::new();
+        schema.add_entity(Entity::new("User".to_string()));
+        schema.add_entity(Entity::new("Order".to_string()));
+
+        let source = schema.to_source();
+        assert!(source.contains("entity User {"));
+        assert!(source.contains("\n\nentity Order {"));
+    }
+
+    // ─── ROUND-TRIP: parse_schema(schema.to_source()) == schema ───
+
+    #[test]
+    fn test_round_trip_plain_entity() {
+        let mut schema = Schema::new();
+        let mut user = Entity::new("User".to_string());
+        user.add_field(Field {
+            name: "id".to_string(), field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        user.add_field(Field {
+            name: "email".to_string(), field_type: FieldType::String,
+            nullable: false, unique: true, primary_key: false,
+            default: None, backend: None,
+        });
+        schema.add_entity(user);
+
+        let source = schema.to_source();
+        let parsed = crate::parser::parse_schema(&source).unwrap();
+        assert_eq!(parsed, schema, "round-trip of:\n{}", source);
+    }
+
+    #[test]
+    fn test_round_trip_namespaced_entity() {
+        let mut schema = Schema::new();
+        let mut order = Entity::from_qualified_name("analytics.Order");
+        order.add_field(Field {
+            name: "id".to_string(), field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        schema.add_entity(order);
+
+        let source = schema.to_source();
+        let parsed = crate::parser::parse_schema(&source).unwrap();
+        assert_eq!(parsed, schema, "round-trip of:\n{}", source);
+    }
+
+    #[test]
+    fn test_round_trip_temporal_entity() {
+        let mut schema = Schema::new();
+        let mut audit_log = Entity::new("AuditLog".to_string());
+        audit_log.temporal = true;
+        audit_log.add_field(Field {
+            name: "id".to_string(), field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        schema.add_entity(audit_log);
+
+        let source = schema.to_source();
+        let parsed = crate::parser::parse_schema(&source).unwrap();
+        assert_eq!(parsed, schema, "round-trip of:\n{}", source);
+    }
+
+    #[test]
+    fn test_round_trip_through_and_via_relations() {
+        let mut schema = Schema::new();
+
+        let mut user = Entity::new("User".to_string());
+        user.add_field(Field {
+            name: "id".to_string(), field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        user.add_relation(Relation {
+            name: "orders".to_string(),
+            kind: RelationKind::HasMany,
+            target_entity: "Order".to_string(),
+            foreign_key: Some("user_id".to_string()),
+            through: None,
+        });
+        user.add_relation(Relation {
+            name: "roles".to_string(),
+            kind: RelationKind::ManyToMany,
+            target_entity: "Role".to_string(),
+            foreign_key: None,
+            through: Some("user_roles".to_string()),
+        });
+        schema.add_entity(user);
+
+        let source = schema.to_source();
+        let parsed = crate::parser::parse_schema(&source).unwrap();
+        assert_eq!(parsed, schema, "round-trip of:\n{}", source);
+    }
+
+    #[test]
+    fn test_round_trip_annotations_and_defaults() {
+        let mut schema = Schema::new();
+        let mut product = Entity::new("Product".to_string());
+        product.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: Some(DefaultValue::UUIDv4),
+            backend: None,
+        });
+        product.add_field(Field {
+            name: "views".to_string(),
+            field_type: FieldType::Int,
+            nullable: true, unique: false, primary_key: false,
+            default: None,
+            backend: Some(BackendAnnotation::Cache),
+        });
+        product.add_field(Field {
+            name: "embedding".to_string(),
+            field_type: FieldType::Vector(384),
+            nullable: false, unique: false, primary_key: false,
+            default: None,
+            backend: Some(BackendAnnotation::Vector),
+        });
+        schema.add_entity(product);
+
+        let source = schema.to_source();
+        let parsed = crate::parser::parse_schema(&source).unwrap();
+        assert_eq!(parsed, schema, "round-trip of:\n{}", source);
+    }
 }
\ No newline at end of file
@@ -0,0 +1,236 @@
+pub mod render;
+
+use std::collections::HashMap;
+
+use tokio_postgres::Client;
+
+use crate::ast::{DefaultValue, Entity, Field, Relation, RelationKind, Schema};
+use crate::migration::type_map::{from_postgres_default, from_postgres_type};
+use crate::sql::naming::table_to_entity;
+
+/// Reverse-engineer a `Schema` from a live PostgreSQL database.
+///
+/// Mirrors what `diesel print_schema` does for Rust: walk
+/// `information_schema` to recover tables, columns, keys and foreign keys,
+/// then map everything back into ChameleonDB's AST so it can be rendered
+/// to `.cham` source with `render::to_source`.
+pub async fn introspect_schema(client: &Client, filter: &Filtering) -> Result<Schema, IntrospectError> {
+    let mut schema = Schema::new();
+
+    let table_names = list_tables(client, filter).await?;
+    let primary_keys = load_primary_keys(client, &table_names).await?;
+    let unique_columns = load_unique_columns(client, &table_names).await?;
+    let foreign_keys = load_foreign_keys(client, &table_names).await?;
+
+    for table_name in &table_names {
+        let entity_name = table_to_entity(table_name);
+        let mut entity = Entity::new(entity_name.clone());
+
+        for field in load_columns(client, table_name, &primary_keys, &unique_columns).await? {
+            entity.add_field(field);
+        }
+
+        schema.add_entity(entity);
+    }
+
+    // Foreign keys become BelongsTo (child → parent) / HasMany (parent → child)
+    // relation pairs once every entity exists, so target lookups resolve.
+    for fk in &foreign_keys {
+        let child_entity_name = table_to_entity(&fk.table);
+        let parent_entity_name = table_to_entity(&fk.referenced_table);
+
+        if let Some(child) = schema.get_entity_mut(&child_entity_name) {
+            child.add_relation(Relation {
+                name: parent_entity_name.to_lowercase(),
+                kind: RelationKind::BelongsTo,
+                target_entity: parent_entity_name.clone(),
+                foreign_key: None,
+                through: None,
+            });
+        }
+
+        if let Some(parent) = schema.get_entity_mut(&parent_entity_name) {
+            parent.add_relation(Relation {
+                name: fk.table.clone(),
+                kind: RelationKind::HasMany,
+                target_entity: child_entity_name.clone(),
+                foreign_key: Some(fk.column.clone()),
+                through: None,
+            });
+        }
+    }
+
+    Ok(schema)
+}
+
+/// Mirrors diesel's `Filtering`: scope introspection to a subset of tables.
+/// `only_tables` wins if non-empty; otherwise every table not named in
+/// `except_tables` is introspected.
+#[derive(Debug, Clone, Default)]
+pub struct Filtering {
+    pub only_tables: Vec<String>,
+    pub except_tables: Vec<String>,
+}
+
+impl Filtering {
+    fn allows(&self, table_name: &str) -> bool {
+        if !self.only_tables.is_empty() {
+            return self.only_tables.iter().any(|t| t == table_name);
+        }
+        !self.except_tables.iter().any(|t| t == table_name)
+    }
+}
+
+struct ForeignKeyRef {
+    table: String,
+    column: String,
+    referenced_table: String,
+}
+
+async fn list_tables(client: &Client, filter: &Filtering) -> Result<Vec<String>, IntrospectError> {
+    let rows = client.query(
+        "SELECT table_name FROM information_schema.tables \
+         WHERE table_schema = 'public' AND table_type = 'BASE TABLE' \
+         ORDER BY table_name",
+        &[],
+    ).await?;
+
+    Ok(rows.iter()
+        .map(|row| row.get::<_, String>("table_name"))
+        .filter(|name| filter.allows(name))
+        .collect())
+}
+
+async fn load_columns(
+    client: &Client,
+    table_name: &str,
+    primary_keys: &HashMap<String, Vec<String>>,
+    unique_columns: &HashMap<String, Vec<String>>,
+) -> Result<Vec<Field>, IntrospectError> {
+    let rows = client.query(
+        "SELECT c.column_name, c.udt_name, c.data_type, c.is_nullable, c.column_default, a.atttypmod \
+         FROM information_schema.columns c \
+         JOIN pg_attribute a \
+           ON a.attrelid = format('%I.%I', c.table_schema, c.table_name)::regclass \
+          AND a.attname = c.column_name \
+         WHERE c.table_schema = 'public' AND c.table_name = $1 \
+         ORDER BY c.ordinal_position",
+        &[&table_name],
+    ).await?;
+
+    let pk_columns = primary_keys.get(table_name).cloned().unwrap_or_default();
+    let uq_columns = unique_columns.get(table_name).cloned().unwrap_or_default();
+
+    let mut fields = Vec::new();
+    for row in rows {
+        let column_name: String = row.get("column_name");
+        let udt_name: String = row.get("udt_name");
+        let data_type: String = row.get("data_type");
+        let is_nullable: String = row.get("is_nullable");
+        let column_default: Option<String> = row.get("column_default");
+        let atttypmod: i32 = row.get("atttypmod");
+        // `atttypmod` is `-1` when the column's type carries no modifier
+        // (the common case); a real modifier (e.g. pgvector's dimension)
+        // is a non-negative value.
+        let typmod = if atttypmod >= 0 { Some(atttypmod) } else { None };
+
+        let field_type = if data_type == "ARRAY" {
+            // Postgres reports array element types with a leading underscore
+            // (e.g. `_varchar` for `varchar[]`).
+            crate::ast::FieldType::Array(Box::new(from_postgres_type(udt_name.trim_start_matches('_'), typmod)))
+        } else {
+            from_postgres_type(&udt_name, typmod)
+        };
+
+        fields.push(Field {
+            primary_key: pk_columns.contains(&column_name),
+            unique: uq_columns.contains(&column_name),
+            nullable: is_nullable == "YES",
+            default: column_default.as_deref().map(parse_default),
+            name: column_name,
+            field_type,
+            backend: None,
+        });
+    }
+
+    Ok(fields)
+}
+
+fn parse_default(expr: &str) -> DefaultValue {
+    from_postgres_default(expr)
+}
+
+async fn load_primary_keys(
+    client: &Client,
+    table_names: &[String],
+) -> Result<HashMap<String, Vec<String>>, IntrospectError> {
+    load_constraint_columns(client, table_names, "PRIMARY KEY").await
+}
+
+async fn load_unique_columns(
+    client: &Client,
+    table_names: &[String],
+) -> Result<HashMap<String, Vec<String>>, IntrospectError> {
+    load_constraint_columns(client, table_names, "UNIQUE").await
+}
+
+async fn load_constraint_columns(
+    client: &Client,
+    table_names: &[String],
+    constraint_type: &str,
+) -> Result<HashMap<String, Vec<String>>, IntrospectError> {
+    let rows = client.query(
+        "SELECT tc.table_name, kcu.column_name \
+         FROM information_schema.table_constraints tc \
+         JOIN information_schema.key_column_usage kcu \
+           ON tc.constraint_name = kcu.constraint_name \
+          AND tc.table_schema = kcu.table_schema \
+         WHERE tc.table_schema = 'public' AND tc.constraint_type = $1",
+        &[&constraint_type],
+    ).await?;
+
+    let mut by_table: HashMap<String, Vec<String>> = HashMap::new();
+    for row in rows {
+        let table_name: String = row.get("table_name");
+        if !table_names.iter().any(|t| t == &table_name) {
+            continue;
+        }
+        let column_name: String = row.get("column_name");
+        by_table.entry(table_name).or_default().push(column_name);
+    }
+    Ok(by_table)
+}
+
+async fn load_foreign_keys(
+    client: &Client,
+    table_names: &[String],
+) -> Result<Vec<ForeignKeyRef>, IntrospectError> {
+    let rows = client.query(
+        "SELECT tc.table_name, kcu.column_name, ccu.table_name AS referenced_table \
+         FROM information_schema.table_constraints tc \
+         JOIN information_schema.key_column_usage kcu \
+           ON tc.constraint_name = kcu.constraint_name \
+          AND tc.table_schema = kcu.table_schema \
+         JOIN information_schema.constraint_column_usage ccu \
+           ON tc.constraint_name = ccu.constraint_name \
+          AND tc.table_schema = ccu.table_schema \
+         WHERE tc.table_schema = 'public' AND tc.constraint_type = 'FOREIGN KEY'",
+        &[],
+    ).await?;
+
+    Ok(rows.iter()
+        .map(|row| ForeignKeyRef {
+            table: row.get("table_name"),
+            column: row.get("column_name"),
+            referenced_table: row.get("referenced_table"),
+        })
+        .filter(|fk| table_names.iter().any(|t| t == &fk.table))
+        .collect())
+}
+
+/// Introspection errors
+#[derive(Debug, thiserror::Error)]
+pub enum IntrospectError {
+    #[error("database error during introspection: {0}")]
+    Database(#[from] tokio_postgres::Error),
+}
@@ -0,0 +1,12 @@
+use crate::ast::Schema;
+
+/// Render a `Schema` back into `.cham` source text.
+///
+/// This is the counterpart to `introspect_schema`: it lets users adopt
+/// ChameleonDB on top of an existing database by dumping the recovered
+/// schema straight into a `.cham` file they can keep editing. It's a thin
+/// wrapper over `Schema::to_source`, the same pretty-printer used to render
+/// programmatically built schemas.
+pub fn to_source(schema: &Schema) -> String {
+    schema.to_source()
+}
@@ -0,0 +1,93 @@
+use std::sync::Arc;
+
+use arrow::datatypes::{DataType, Field as ArrowField, Schema as ArrowSchema, TimeUnit};
+
+use crate::ast::{Entity, FieldType};
+
+/// Maps a ChameleonDB field type to an Arrow `DataType`, so entities can
+/// drive columnar export/ingest pipelines (Parquet, Flight, DataFusion)
+/// straight from a `.cham` definition.
+pub fn to_arrow_type(field_type: &FieldType) -> DataType {
+    match field_type {
+        FieldType::UUID => DataType::FixedSizeBinary(16),
+        FieldType::String => DataType::Utf8,
+        FieldType::Int => DataType::Int64,
+        FieldType::Decimal => DataType::Decimal128(38, 9),
+        FieldType::Bool => DataType::Boolean,
+        FieldType::Timestamp => DataType::Timestamp(TimeUnit::Microsecond, None),
+        FieldType::Float => DataType::Float64,
+        FieldType::Vector(dim) => DataType::FixedSizeList(
+            Arc::new(ArrowField::new("item", DataType::Float32, false)),
+            *dim as i32,
+        ),
+        FieldType::Array(inner) => DataType::List(
+            Arc::new(ArrowField::new("item", to_arrow_type(inner), true)),
+        ),
+    }
+}
+
+/// Build the Arrow `Schema` for an entity, with each field's `nullable`
+/// carried over. Field order is alphabetical by name, since `Entity::fields`
+/// is a `HashMap` with no inherent order.
+pub fn to_arrow_schema(entity: &Entity) -> ArrowSchema {
+    let mut names: Vec<&String> = entity.fields.keys().collect();
+    names.sort();
+
+    let fields: Vec<ArrowField> = names.iter()
+        .map(|name| {
+            let field = &entity.fields[*name];
+            ArrowField::new(field.name.clone(), to_arrow_type(&field.field_type), field.nullable)
+        })
+        .collect();
+
+    ArrowSchema::new(fields)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::*;
+
+    #[test]
+    fn test_to_arrow_type_basic() {
+        assert_eq!(to_arrow_type(&FieldType::UUID), DataType::FixedSizeBinary(16));
+        assert_eq!(to_arrow_type(&FieldType::String), DataType::Utf8);
+        assert_eq!(to_arrow_type(&FieldType::Int), DataType::Int64);
+        assert_eq!(to_arrow_type(&FieldType::Bool), DataType::Boolean);
+        assert_eq!(to_arrow_type(&FieldType::Float), DataType::Float64);
+    }
+
+    #[test]
+    fn test_to_arrow_type_vector_is_fixed_size_list_of_f32() {
+        match to_arrow_type(&FieldType::Vector(384)) {
+            DataType::FixedSizeList(field, size) => {
+                assert_eq!(*field.data_type(), DataType::Float32);
+                assert_eq!(size, 384);
+            }
+            other => panic!("expected FixedSizeList, got {:?}", other),
+        }
+    }
+
+    #[test]
+    fn test_to_arrow_schema_carries_nullability() {
+        let mut entity = Entity::new("User".to_string());
+        entity.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        entity.add_field(Field {
+            name: "age".to_string(),
+            field_type: FieldType::Int,
+            nullable: true, unique: false, primary_key: false,
+            default: None, backend: None,
+        });
+
+        let schema = to_arrow_schema(&entity);
+        assert_eq!(schema.field(0).name(), "age");
+        assert!(schema.field(0).is_nullable());
+        assert_eq!(schema.field(1).name(), "id");
+        assert!(!schema.field(1).is_nullable());
+    }
+}
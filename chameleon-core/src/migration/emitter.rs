@@ -0,0 +1,339 @@
+use crate::ast::{BackendAnnotation, DefaultValue, Field, FieldType, Schema};
+use crate::sql::naming::entity_to_table;
+use super::iceberg::to_iceberg_type;
+use super::type_map::{to_postgres_default, to_postgres_type};
+
+/// Which distance function an ANN vector index compares embeddings with —
+/// selects the pgvector operator class `index_ddl` builds the index with.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorDistance {
+    L2,
+    Cosine,
+    InnerProduct,
+}
+
+impl VectorDistance {
+    fn opclass(&self) -> &'static str {
+        match self {
+            VectorDistance::L2 => "vector_l2_ops",
+            VectorDistance::Cosine => "vector_cosine_ops",
+            VectorDistance::InnerProduct => "vector_ip_ops",
+        }
+    }
+}
+
+/// Which approximate-nearest-neighbor index structure to build for a
+/// `@vector` field.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum VectorIndexKind {
+    Hnsw,
+    IvfFlat,
+}
+
+impl VectorIndexKind {
+    fn using_clause(&self) -> &'static str {
+        match self {
+            VectorIndexKind::Hnsw => "hnsw",
+            VectorIndexKind::IvfFlat => "ivfflat",
+        }
+    }
+}
+
+/// Per-backend codegen: a column type, a default-value expression, an
+/// optional companion index, and a full table definition for the fields an
+/// entity routes to this backend. `generate_backend_ddl` picks one
+/// implementation per `BackendAnnotation` and fans a single `Schema` out
+/// into one DDL script per (entity, backend) pair.
+pub trait BackendEmitter {
+    fn column_type(&self, field_type: &FieldType) -> String;
+    fn default_expr(&self, default: &DefaultValue) -> String;
+
+    /// A companion index statement for `field`, if this backend indexes it
+    /// beyond the column itself (e.g. an ANN index on a vector column).
+    /// `None` when the field needs no such index.
+    fn index_ddl(&self, table: &str, field: &Field) -> Option<String>;
+
+    /// The full DDL for a table made of `fields` on this backend, including
+    /// any companion index statements `index_ddl` produces for them.
+    fn table_ddl(&self, table: &str, fields: &[&Field]) -> String;
+}
+
+/// The default backend: every field lands as a plain Postgres column,
+/// exactly as `generate_create_table` already builds the live OLTP table.
+pub struct PostgresEmitter;
+
+impl BackendEmitter for PostgresEmitter {
+    fn column_type(&self, field_type: &FieldType) -> String {
+        to_postgres_type(field_type)
+    }
+
+    fn default_expr(&self, default: &DefaultValue) -> String {
+        to_postgres_default(default)
+    }
+
+    fn index_ddl(&self, _table: &str, _field: &Field) -> Option<String> {
+        None
+    }
+
+    fn table_ddl(&self, table: &str, fields: &[&Field]) -> String {
+        let mut columns: Vec<String> = fields.iter().map(|field| {
+            let mut col = format!("    {} {}", field.name, self.column_type(&field.field_type));
+            if field.primary_key {
+                col.push_str(" PRIMARY KEY");
+            }
+            if !field.nullable && !field.primary_key {
+                col.push_str(" NOT NULL");
+            }
+            if field.unique {
+                col.push_str(" UNIQUE");
+            }
+            if let Some(default) = &field.default {
+                col.push_str(&format!(" DEFAULT {}", self.default_expr(default)));
+            }
+            col
+        }).collect();
+        columns.sort();
+
+        format!("CREATE TABLE {} (\n{}\n);", table, columns.join(",\n"))
+    }
+}
+
+/// Targets `@olap` fields at a columnar analytic engine, using the same
+/// type mapping `generate_iceberg_schema` uses for its catalog JSON, but
+/// rendered as a `STORED AS ICEBERG` table definition a query engine like
+/// Trino or Dremio can run directly — complementary to, not a replacement
+/// for, the structured `IcebergTable` schema.
+pub struct OlapEmitter;
+
+impl BackendEmitter for OlapEmitter {
+    fn column_type(&self, field_type: &FieldType) -> String {
+        to_iceberg_type(field_type)
+    }
+
+    fn default_expr(&self, default: &DefaultValue) -> String {
+        to_postgres_default(default)
+    }
+
+    fn index_ddl(&self, _table: &str, _field: &Field) -> Option<String> {
+        None
+    }
+
+    fn table_ddl(&self, table: &str, fields: &[&Field]) -> String {
+        let mut columns: Vec<String> = fields.iter()
+            .map(|field| format!("    {} {}", field.name, self.column_type(&field.field_type)))
+            .collect();
+        columns.sort();
+
+        format!("CREATE TABLE {} (\n{}\n) STORED AS ICEBERG;", table, columns.join(",\n"))
+    }
+}
+
+/// Targets `@vector` fields at a pgvector column plus a companion
+/// approximate-nearest-neighbor index, so similarity search isn't stuck
+/// with an exact scan over every row.
+pub struct VectorEmitter {
+    pub index_kind: VectorIndexKind,
+    pub distance: VectorDistance,
+}
+
+impl VectorEmitter {
+    pub fn new(index_kind: VectorIndexKind, distance: VectorDistance) -> Self {
+        VectorEmitter { index_kind, distance }
+    }
+}
+
+impl Default for VectorEmitter {
+    /// HNSW over cosine distance: pgvector's own recommendation for
+    /// embedding similarity search, absent a reason to pick otherwise.
+    fn default() -> Self {
+        VectorEmitter::new(VectorIndexKind::Hnsw, VectorDistance::Cosine)
+    }
+}
+
+impl BackendEmitter for VectorEmitter {
+    fn column_type(&self, field_type: &FieldType) -> String {
+        to_postgres_type(field_type)
+    }
+
+    fn default_expr(&self, default: &DefaultValue) -> String {
+        to_postgres_default(default)
+    }
+
+    fn index_ddl(&self, table: &str, field: &Field) -> Option<String> {
+        if !matches!(field.field_type, FieldType::Vector(_)) {
+            return None;
+        }
+        Some(format!(
+            "CREATE INDEX {table}_{field}_idx ON {table} USING {using} ({field} {opclass});",
+            table = table,
+            field = field.name,
+            using = self.index_kind.using_clause(),
+            opclass = self.distance.opclass(),
+        ))
+    }
+
+    fn table_ddl(&self, table: &str, fields: &[&Field]) -> String {
+        let mut columns: Vec<String> = fields.iter()
+            .map(|field| format!("    {} {}", field.name, self.column_type(&field.field_type)))
+            .collect();
+        columns.sort();
+
+        let mut statements = vec![format!("CREATE TABLE {} (\n{}\n);", table, columns.join(",\n"))];
+
+        for field in fields {
+            if let Some(index) = self.index_ddl(table, field) {
+                statements.push(index);
+            }
+        }
+
+        statements.join("\n\n")
+    }
+}
+
+/// The emitter that owns a given `BackendAnnotation`'s own table, or `None`
+/// for annotations that stay on the live OLTP table (`OLTP`, `Cache`, `ML`,
+/// `History`) rather than fanning out into a separate DDL script.
+fn emitter_for(annotation: &BackendAnnotation) -> Option<Box<dyn BackendEmitter>> {
+    match annotation {
+        BackendAnnotation::OLAP => Some(Box::new(OlapEmitter)),
+        BackendAnnotation::Vector => Some(Box::new(VectorEmitter::default())),
+        BackendAnnotation::OLTP
+        | BackendAnnotation::Cache
+        | BackendAnnotation::ML
+        | BackendAnnotation::History => None,
+    }
+}
+
+/// Fan a schema out into one DDL script per (entity, backend) pair, for
+/// every `BackendAnnotation` that targets its own engine. A `@vector` or
+/// `@olap` field still gets a column on the main OLTP table via
+/// `generate_create_table` — this is the *additional* backend-specific
+/// table those fields also need, the same way `generate_iceberg_migration`
+/// emits its catalog schema alongside (not instead of) the Postgres DDL.
+///
+/// Entities with no fields routed to a given backend contribute nothing;
+/// each script is labeled with its entity's name, and the table it creates
+/// is named `"{table}_{suffix}"` (e.g. `"products_olap"`, `"products_vectors"`).
+pub fn generate_backend_ddl(schema: &Schema) -> Vec<(String, String)> {
+    let mut scripts = Vec::new();
+
+    // Only annotations that route to their own table (see `emitter_for`)
+    // need a suffix here — the rest never reach `table_ddl`.
+    let routed_backends = [
+        (BackendAnnotation::OLAP, "olap"),
+        (BackendAnnotation::Vector, "vectors"),
+    ];
+
+    for entity in &schema.entities {
+        for (annotation, suffix) in &routed_backends {
+            let mut fields: Vec<&Field> = entity.fields.values()
+                .filter(|field| field.backend.as_ref() == Some(annotation))
+                .collect();
+            if fields.is_empty() {
+                continue;
+            }
+            fields.sort_by_key(|f| f.name.clone());
+
+            let Some(emitter) = emitter_for(annotation) else { continue };
+            let table = entity_to_table(&entity.name);
+            let backend_table = format!("{}_{}", table, suffix);
+            scripts.push((entity.name.clone(), emitter.table_ddl(&backend_table, &fields)));
+        }
+    }
+
+    scripts
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::*;
+
+    fn field(name: &str, field_type: FieldType, backend: Option<BackendAnnotation>) -> Field {
+        Field {
+            name: name.to_string(),
+            field_type,
+            nullable: false,
+            unique: false,
+            primary_key: false,
+            default: None,
+            backend,
+        }
+    }
+
+    #[test]
+    fn test_postgres_emitter_matches_generate_create_table_column_style() {
+        let emitter = PostgresEmitter;
+        let id = field("id", FieldType::UUID, None);
+        let ddl = emitter.table_ddl("users", &[&id]);
+
+        assert!(ddl.starts_with("CREATE TABLE users (\n"));
+        assert!(ddl.contains("id UUID"));
+    }
+
+    #[test]
+    fn test_olap_emitter_renders_iceberg_types_and_stored_as_clause() {
+        let emitter = OlapEmitter;
+        let sales = field("monthly_sales", FieldType::Decimal, Some(BackendAnnotation::OLAP));
+        let ddl = emitter.table_ddl("orders_olap", &[&sales]);
+
+        assert!(ddl.contains("monthly_sales decimal(38,9)"));
+        assert!(ddl.ends_with("STORED AS ICEBERG;"));
+    }
+
+    #[test]
+    fn test_vector_emitter_default_is_hnsw_cosine() {
+        let emitter = VectorEmitter::default();
+        let embedding = field("embedding", FieldType::Vector(384), Some(BackendAnnotation::Vector));
+        let index = emitter.index_ddl("products_vectors", &embedding).unwrap();
+
+        assert!(index.contains("USING hnsw"));
+        assert!(index.contains("vector_cosine_ops"));
+    }
+
+    #[test]
+    fn test_vector_emitter_table_ddl_includes_column_and_index() {
+        let emitter = VectorEmitter::new(VectorIndexKind::IvfFlat, VectorDistance::L2);
+        let embedding = field("embedding", FieldType::Vector(1536), Some(BackendAnnotation::Vector));
+        let ddl = emitter.table_ddl("products_vectors", &[&embedding]);
+
+        assert!(ddl.contains("CREATE TABLE products_vectors"));
+        assert!(ddl.contains("embedding VECTOR(1536)"));
+        assert!(ddl.contains("CREATE INDEX products_vectors_embedding_idx"));
+        assert!(ddl.contains("USING ivfflat"));
+        assert!(ddl.contains("vector_l2_ops"));
+    }
+
+    #[test]
+    fn test_vector_emitter_index_ddl_is_none_for_non_vector_field() {
+        let emitter = VectorEmitter::default();
+        let name = field("name", FieldType::String, None);
+        assert!(emitter.index_ddl("products_vectors", &name).is_none());
+    }
+
+    #[test]
+    fn test_generate_backend_ddl_fans_out_olap_and_vector_scripts() {
+        let mut schema = Schema::new();
+        let mut product = Entity::new("Product".to_string());
+        product.add_field(field("id", FieldType::UUID, None));
+        product.add_field(field("monthly_sales", FieldType::Decimal, Some(BackendAnnotation::OLAP)));
+        product.add_field(field("embedding", FieldType::Vector(384), Some(BackendAnnotation::Vector)));
+        schema.add_entity(product);
+
+        let scripts = generate_backend_ddl(&schema);
+
+        assert_eq!(scripts.len(), 2);
+        assert!(scripts.iter().any(|(_, ddl)| ddl.contains("STORED AS ICEBERG")));
+        assert!(scripts.iter().any(|(_, ddl)| ddl.contains("USING hnsw")));
+    }
+
+    #[test]
+    fn test_generate_backend_ddl_skips_entities_with_no_backend_fields() {
+        let mut schema = Schema::new();
+        let mut user = Entity::new("User".to_string());
+        user.add_field(field("id", FieldType::UUID, None));
+        schema.add_entity(user);
+
+        assert!(generate_backend_ddl(&schema).is_empty());
+    }
+}
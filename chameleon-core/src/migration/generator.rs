@@ -1,4 +1,4 @@
-use crate::ast::{Schema, Entity, RelationKind};
+use crate::ast::{BackendAnnotation, Entity, Field, RelationKind, Schema};
 use crate::sql::naming::entity_to_table;
 use super::type_map::{to_postgres_type, to_postgres_default};
 
@@ -9,6 +9,10 @@ pub struct Migration {
     pub sql: String,
     /// Ordered list of (entity_name, CREATE TABLE statement)
     pub statements: Vec<(String, String)>,
+    /// Complete DDL script that undoes `sql`, ready to execute on its own
+    pub down_sql: String,
+    /// Ordered list of (entity_name, statement) that undoes `statements`
+    pub down_statements: Vec<(String, String)>,
 }
 
 /// Generate a full migration from a validated schema
@@ -22,20 +26,99 @@ pub fn generate_migration(schema: &Schema) -> Result<Migration, MigrationError>
         let entity = schema.get_entity(entity_name).unwrap();
         let sql = generate_create_table(entity, schema)?;
         statements.push((entity_name.clone(), sql));
+
+        // Bitemporal support: history table + versioning triggers, right
+        // after the entity's own table, which the trigger and the as-of
+        // function refer to.
+        if entity.is_bitemporal() {
+            for sql in generate_temporal_support(entity)? {
+                statements.push((entity_name.clone(), sql));
+            }
+        }
     }
 
-    // 3. Join into full script
+    // 3. Join tables for ManyToMany relations, after both endpoints exist
+    let join_tables = generate_join_tables(schema)?;
+    let mut down_statements: Vec<(String, String)> = join_tables.iter().rev()
+        .map(|(name, _)| (name.clone(), format!("DROP TABLE IF EXISTS {} CASCADE;", name)))
+        .collect();
+    statements.extend(join_tables);
+
+    // 4. Join into full script
     let sql = statements.iter()
         .map(|(_, stmt)| stmt.as_str())
         .collect::<Vec<&str>>()
         .join("\n\n");
 
-    Ok(Migration { sql, statements })
+    // 5. The down migration just drops every table this migration created,
+    //    in the reverse of the order it created them in, so referencing
+    //    tables go before the tables they reference: join tables first,
+    //    then each entity's table with its history table and versioning
+    //    function.
+    for entity_name in order.iter().rev() {
+        let entity = schema.get_entity(entity_name).unwrap();
+        down_statements.push((entity_name.clone(), format!("DROP TABLE IF EXISTS {} CASCADE;", qualified_table_name(entity))));
+        if entity.is_bitemporal() {
+            let table = entity_to_table(&entity.name);
+            down_statements.push((entity_name.clone(), format!("DROP TABLE IF EXISTS {}_history CASCADE;", table)));
+            down_statements.push((entity_name.clone(), format!("DROP FUNCTION IF EXISTS {}_version_row() CASCADE;", table)));
+        }
+    }
+
+    let down_sql = down_statements.iter()
+        .map(|(_, stmt)| stmt.as_str())
+        .collect::<Vec<&str>>()
+        .join("\n\n");
+
+    Ok(Migration { sql, statements, down_sql, down_statements })
+}
+
+/// Reserved words that must be quoted when they appear as a table or schema
+/// identifier (not exhaustive — just the ones likely to collide with
+/// entity/table names in practice).
+const RESERVED_WORDS: &[&str] = &[
+    "order", "group", "user", "table", "select", "where", "primary", "references",
+];
+
+/// Quote a SQL identifier if it contains a dot or collides with a reserved
+/// word; otherwise return it unchanged.
+fn quote_identifier(identifier: &str) -> String {
+    let needs_quoting = identifier.contains('.')
+        || RESERVED_WORDS.contains(&identifier.to_lowercase().as_str());
+    if needs_quoting {
+        format!("\"{}\"", identifier)
+    } else {
+        identifier.to_string()
+    }
+}
+
+/// The fully-qualified table name for an entity, e.g. `analytics.orders`
+/// for an entity namespaced as `analytics.Order`, with each part quoted if
+/// needed.
+pub(super) fn qualified_table_name(entity: &Entity) -> String {
+    let table = entity_to_table(&entity.name);
+    match &entity.namespace {
+        Some(ns) => format!("{}.{}", quote_identifier(ns), quote_identifier(&table)),
+        None => quote_identifier(&table),
+    }
+}
+
+/// Deterministic name for a foreign key constraint, so later ALTERs (e.g. a
+/// diff that needs to drop and re-add it around a type change) can refer
+/// back to the constraint `generate_create_table` created.
+pub(super) fn fk_constraint_name(table: &str, fk_column: &str) -> String {
+    format!("fk_{}_{}", table.trim_matches('"'), fk_column)
+}
+
+/// Whether a relation's `target_entity` (e.g. `"Order"` or
+/// `"analytics.Order"`) refers to this entity.
+pub(crate) fn target_matches(entity: &Entity, target_entity: &str) -> bool {
+    target_entity == entity.name || target_entity == entity.qualified_name()
 }
 
 /// Generate a single CREATE TABLE statement
-fn generate_create_table(entity: &Entity, schema: &Schema) -> Result<String, MigrationError> {
-    let table_name = entity_to_table(&entity.name);
+pub(super) fn generate_create_table(entity: &Entity, schema: &Schema) -> Result<String, MigrationError> {
+    let table_name = qualified_table_name(entity);
     let mut columns = Vec::new();
     let mut constraints = Vec::new();
 
@@ -68,6 +151,13 @@ fn generate_create_table(entity: &Entity, schema: &Schema) -> Result<String, Mig
         columns.push(col);
     }
 
+    // Bitemporal entities carry the validity range on the live table itself
+    // so "as of now" reads are just the normal table.
+    if entity.is_bitemporal() {
+        columns.push("    valid_from TIMESTAMPTZ NOT NULL DEFAULT NOW()".to_string());
+        columns.push("    valid_to TIMESTAMPTZ".to_string());
+    }
+
     // Foreign key constraints from HasMany relations in OTHER entities
     // that point TO this entity
     for other_entity in &schema.entities {
@@ -89,15 +179,15 @@ fn generate_create_table(entity: &Entity, schema: &Schema) -> Result<String, Mig
     for other_entity in &schema.entities {
         for (_, relation) in &other_entity.relations {
             if relation.kind == RelationKind::HasMany
-                && relation.target_entity == entity.name
+                && target_matches(entity, &relation.target_entity)
             {
                 // other_entity HasMany this entity via FK
                 // The FK field is IN this entity
                 if let Some(fk) = &relation.foreign_key {
-                    let other_table = entity_to_table(&other_entity.name);
+                    let other_table = qualified_table_name(other_entity);
                     constraints.push(format!(
-                        "    FOREIGN KEY ({}) REFERENCES {}(id)",
-                        fk, other_table
+                        "    CONSTRAINT {} FOREIGN KEY ({}) REFERENCES {}(id)",
+                        fk_constraint_name(&entity_to_table(&entity.name), fk), fk, other_table
                     ));
                 }
             }
@@ -115,9 +205,212 @@ fn generate_create_table(entity: &Entity, schema: &Schema) -> Result<String, Mig
     ))
 }
 
+/// Generate the association table for every `ManyToMany` relation that
+/// names a `through` table. Each join table gets two FK columns (one per
+/// side), a composite primary key over both, and a `FOREIGN KEY` clause per
+/// side — the standard tags/categories shape.
+///
+/// A `through` name is only emitted once even if both sides declare the
+/// relation, since they refer to the same join table.
+fn generate_join_tables(schema: &Schema) -> Result<Vec<(String, String)>, MigrationError> {
+    let mut seen = Vec::new();
+    let mut statements = Vec::new();
+
+    for entity in &schema.entities {
+        for relation in entity.relations.values() {
+            if relation.kind != RelationKind::ManyToMany {
+                continue;
+            }
+            let Some(through) = &relation.through else { continue };
+            if seen.contains(through) {
+                continue;
+            }
+            seen.push(through.clone());
+
+            let left_column = format!("{}_id", to_snake_case(&entity.name));
+            let right_column = format!("{}_id", to_snake_case(&relation.target_entity));
+            let left_table = qualified_table_name(entity);
+            let right_table = schema.entities.iter()
+                .find(|e| target_matches(e, &relation.target_entity))
+                .map(qualified_table_name)
+                .unwrap_or_else(|| entity_to_table(&relation.target_entity));
+
+            let sql = format!(
+                "CREATE TABLE {} (\n    {} UUID NOT NULL,\n    {} UUID NOT NULL,\n    PRIMARY KEY ({}, {}),\n    FOREIGN KEY ({}) REFERENCES {}(id),\n    FOREIGN KEY ({}) REFERENCES {}(id)\n);",
+                through,
+                left_column, right_column,
+                left_column, right_column,
+                left_column, left_table,
+                right_column, right_table,
+            );
+            statements.push((through.clone(), sql));
+        }
+    }
+
+    Ok(statements)
+}
+
+/// Generate the history table and versioning triggers for a `@temporal`
+/// entity: a `<table>_history` table carrying every live column plus
+/// `valid_from`/`valid_to`/`tx_id`, and a trigger that copies the prior row
+/// version into it on `UPDATE`/`DELETE`, closing its `valid_to` at the
+/// transaction that superseded it.
+fn generate_temporal_support(entity: &Entity) -> Result<Vec<String>, MigrationError> {
+    let table_name = qualified_table_name(entity);
+    let history_table = format!("{}_history", entity_to_table(&entity.name));
+
+    let mut history_columns: Vec<String> = entity.fields.values()
+        .map(|field| format!("    {} {}", field.name, to_postgres_type(&field.field_type)))
+        .collect();
+    history_columns.sort();
+    history_columns.push("    valid_from TIMESTAMPTZ NOT NULL".to_string());
+    history_columns.push("    valid_to TIMESTAMPTZ NOT NULL".to_string());
+    history_columns.push("    tx_id BIGINT NOT NULL".to_string());
+    history_columns.push("    op VARCHAR NOT NULL".to_string());
+
+    let create_history = format!(
+        "CREATE TABLE {} (\n{}\n);",
+        history_table,
+        history_columns.join(",\n")
+    );
+
+    // Every UPDATE/DELETE retracts the row version it replaces. An UPDATE
+    // also asserts the new version, so the full timeline — including the
+    // live row's current version — can be reconstructed from history alone.
+    let function_name = format!("{}_version_row", entity_to_table(&entity.name));
+    let trigger_function = format!(
+        "CREATE FUNCTION {function}() RETURNS TRIGGER AS $$\nBEGIN\n    INSERT INTO {history} SELECT OLD.*, OLD.valid_from, NOW(), txid_current(), 'retract';\n    IF TG_OP = 'UPDATE' THEN\n        INSERT INTO {history} SELECT NEW.*, OLD.valid_from, NOW(), txid_current(), 'assert';\n    END IF;\n    RETURN NULL;\nEND;\n$$ LANGUAGE plpgsql;",
+        function = function_name,
+        history = history_table,
+    );
+
+    let trigger = format!(
+        "CREATE TRIGGER {table}_versioning\n    BEFORE UPDATE OR DELETE ON {table}\n    FOR EACH ROW EXECUTE FUNCTION {function}();",
+        table = table_name,
+        function = function_name,
+    );
+
+    // A parameterized "as-of" helper: plain SQL can't take a runtime
+    // parameter in a `CREATE VIEW`, so this is a `STABLE SQL` function
+    // instead, wrapping the same WHERE clause `as_of_query` builds inline.
+    let as_of_function_name = format!("{}_as_of", entity_to_table(&entity.name));
+    let as_of_function = format!(
+        "CREATE FUNCTION {function}(at_timestamp TIMESTAMPTZ) RETURNS SETOF {table} AS $$\n    {query};\n$$ LANGUAGE sql STABLE;",
+        function = as_of_function_name,
+        table = table_name,
+        query = as_of_query(entity, "at_timestamp"),
+    );
+
+    Ok(vec![create_history, trigger_function, trigger, as_of_function])
+}
+
+/// Build the as-of-time read query for a `@temporal` entity: the row(s)
+/// whose validity range covered `at_timestamp`. `at_timestamp` is inserted
+/// verbatim, so callers should pass a parameter placeholder (e.g. `$1`)
+/// rather than an untrusted literal.
+pub fn as_of_query(entity: &Entity, at_timestamp: &str) -> String {
+    let table_name = qualified_table_name(entity);
+    format!(
+        "SELECT * FROM {} WHERE valid_from <= {ts} AND (valid_to IS NULL OR valid_to > {ts})",
+        table_name,
+        ts = at_timestamp,
+    )
+}
+
+/// Which columns `generate_upsert`'s `DO UPDATE SET` clause touches on a
+/// conflict.
+#[derive(Debug, Clone, PartialEq)]
+pub enum UpsertMode {
+    /// Update every non-key OLTP column with its `EXCLUDED` value.
+    Merge,
+    /// Update only the named columns, leaving the rest of the existing row
+    /// untouched.
+    Replace(Vec<String>),
+}
+
+/// Generate an idempotent `INSERT ... ON CONFLICT ... DO UPDATE` upsert for
+/// an entity, keyed on its unique identity. Only fields that live in the
+/// OLTP table participate — `@cache`/`@olap`/`@vector` fields are synced
+/// through their own backend, not this statement.
+pub fn generate_upsert(entity: &Entity) -> Result<String, MigrationError> {
+    generate_upsert_with_mode(entity, &UpsertMode::Merge)
+}
+
+/// `generate_upsert`, with control over which columns the `DO UPDATE SET`
+/// clause touches — see `UpsertMode`.
+pub fn generate_upsert_with_mode(entity: &Entity, mode: &UpsertMode) -> Result<String, MigrationError> {
+    let table_name = qualified_table_name(entity);
+
+    let mut oltp_fields: Vec<&Field> = entity.fields.values()
+        .filter(|field| matches!(field.backend, None | Some(BackendAnnotation::OLTP)))
+        .collect();
+    oltp_fields.sort_by(|a, b| a.name.cmp(&b.name));
+
+    // Unique fields drive the conflict target; fall back to the primary key
+    // when nothing is marked `unique`.
+    let unique_fields: Vec<&Field> = oltp_fields.iter().copied().filter(|f| f.unique).collect();
+    let conflict_fields: Vec<&Field> = if !unique_fields.is_empty() {
+        unique_fields
+    } else {
+        oltp_fields.iter().copied().filter(|f| f.primary_key).collect()
+    };
+
+    // Multiple independent unique fields means multiple valid conflict
+    // targets — we can't pick one, so ask the caller to disambiguate
+    // (e.g. by generating per-field upserts themselves).
+    if conflict_fields.len() != 1 {
+        return Err(MigrationError::AmbiguousConflictTarget(entity.name.clone()));
+    }
+    let conflict_field = conflict_fields[0];
+
+    let column_names: Vec<&str> = oltp_fields.iter().map(|f| f.name.as_str()).collect();
+    let placeholders: Vec<String> = (1..=column_names.len()).map(|i| format!("${}", i)).collect();
+
+    let update_clauses: Vec<String> = oltp_fields.iter()
+        .filter(|f| f.name != conflict_field.name)
+        .filter(|f| match mode {
+            UpsertMode::Merge => true,
+            UpsertMode::Replace(columns) => columns.contains(&f.name),
+        })
+        .map(|f| format!("{} = EXCLUDED.{}", f.name, f.name))
+        .collect();
+
+    let conflict_clause = if update_clauses.is_empty() {
+        "DO NOTHING".to_string()
+    } else {
+        format!("DO UPDATE SET {}", update_clauses.join(", "))
+    };
+
+    Ok(format!(
+        "INSERT INTO {} ({}) VALUES ({}) ON CONFLICT ({}) {};",
+        table_name,
+        column_names.join(", "),
+        placeholders.join(", "),
+        conflict_field.name,
+        conflict_clause,
+    ))
+}
+
+/// Convert a PascalCase entity name into a snake_case identifier
+/// (e.g. "OrderItem" → "order_item"), for synthesizing FK column names.
+fn to_snake_case(name: &str) -> String {
+    let mut out = String::new();
+    for (i, ch) in name.char_indices() {
+        if ch.is_uppercase() {
+            if i > 0 {
+                out.push('_');
+            }
+            out.extend(ch.to_lowercase());
+        } else {
+            out.push(ch);
+        }
+    }
+    out
+}
+
 /// Resolve entity creation order using topological sort
 /// Entities referenced by FKs must be created first
-fn resolve_creation_order(schema: &Schema) -> Result<Vec<String>, MigrationError> {
+pub(super) fn resolve_creation_order(schema: &Schema) -> Result<Vec<String>, MigrationError> {
     let mut order = Vec::new();
     let mut visited = Vec::new();
     let mut in_stack = Vec::new();
@@ -175,6 +468,13 @@ fn topo_sort(
 pub enum MigrationError {
     CircularDependency(String),
     UnknownEntity(String),
+    /// A diff tried to change `primary_key` or drop the PK column outright;
+    /// that can't be expressed as safe DDL, so we refuse instead of guessing.
+    UnsafePrimaryKeyChange(String),
+    /// An entity has more than one independent unique field (or none and no
+    /// primary key), so `generate_upsert` can't pick a single conflict
+    /// target without the caller disambiguating.
+    AmbiguousConflictTarget(String),
 }
 
 impl std::fmt::Display for MigrationError {
@@ -184,6 +484,10 @@ impl std::fmt::Display for MigrationError {
                 write!(f, "Circular dependency detected at '{}'", name),
             MigrationError::UnknownEntity(name) =>
                 write!(f, "Unknown entity: '{}'", name),
+            MigrationError::UnsafePrimaryKeyChange(field) =>
+                write!(f, "Refusing to auto-generate DDL for primary key change on '{}'", field),
+            MigrationError::AmbiguousConflictTarget(entity) =>
+                write!(f, "'{}' has no single conflict target for an upsert: it needs exactly one unique field, or a primary key and no unique field", entity),
         }
     }
 }
\ No newline at end of file
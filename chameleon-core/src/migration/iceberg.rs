@@ -0,0 +1,153 @@
+use serde::Serialize;
+
+use crate::ast::{BackendAnnotation, Entity, FieldType, Schema};
+
+/// Maps a ChameleonDB field type to an Apache Iceberg primitive type, for
+/// `@olap`-annotated fields that target a lakehouse instead of Postgres.
+pub fn to_iceberg_type(field_type: &FieldType) -> String {
+    match field_type {
+        FieldType::UUID => "uuid".to_string(),
+        FieldType::String => "string".to_string(),
+        FieldType::Int => "long".to_string(),
+        FieldType::Decimal => "decimal(38,9)".to_string(),
+        FieldType::Bool => "boolean".to_string(),
+        FieldType::Timestamp => "timestamptz".to_string(),
+        FieldType::Float => "double".to_string(),
+        FieldType::Vector(dim) => format!("fixed[{}]", dim * 4),
+        FieldType::Array(inner) => format!("list<{}>", to_iceberg_type(inner)),
+    }
+}
+
+/// One column of an Iceberg table schema.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IcebergField {
+    pub id: usize,
+    pub name: String,
+    pub required: bool,
+    #[serde(rename = "type")]
+    pub field_type: String,
+}
+
+/// An Iceberg table schema document: `schema-id`, its fields, and a
+/// (currently unpartitioned) partition spec.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IcebergTable {
+    #[serde(rename = "schema-id")]
+    pub schema_id: usize,
+    pub fields: Vec<IcebergField>,
+    #[serde(rename = "partition-spec")]
+    pub partition_spec: Vec<String>,
+}
+
+/// One Iceberg table schema per entity that has at least one `@olap` field.
+#[derive(Debug, Clone, PartialEq, Serialize)]
+pub struct IcebergMigration {
+    pub tables: Vec<(String, IcebergTable)>,
+}
+
+/// Generate the Iceberg table schema for an entity's `@olap` fields, or
+/// `None` if it has none.
+///
+/// Field ids are assigned by sorting field names alphabetically rather
+/// than by `HashMap` iteration order, so the same set of `@olap` fields
+/// always gets the same ids across repeated `generate_iceberg_schema` runs.
+/// Adding a field later that sorts before an existing one will still shift
+/// ids — full stability across schema history would need to persist
+/// assigned ids, which is out of scope here.
+pub fn generate_iceberg_schema(entity: &Entity) -> Option<IcebergTable> {
+    let mut names: Vec<&String> = entity.fields.iter()
+        .filter(|(_, field)| field.backend == Some(BackendAnnotation::OLAP))
+        .map(|(name, _)| name)
+        .collect();
+
+    if names.is_empty() {
+        return None;
+    }
+    names.sort();
+
+    let fields = names.iter().enumerate().map(|(i, name)| {
+        let field = &entity.fields[*name];
+        IcebergField {
+            id: i + 1,
+            name: (*name).clone(),
+            required: !field.nullable,
+            field_type: to_iceberg_type(&field.field_type),
+        }
+    }).collect();
+
+    Some(IcebergTable {
+        schema_id: 0,
+        fields,
+        partition_spec: Vec::new(),
+    })
+}
+
+/// Generate Iceberg table schemas for every `@olap`-tagged entity in the
+/// schema, alongside (not replacing) the Postgres DDL from `generate_migration`.
+pub fn generate_iceberg_migration(schema: &Schema) -> IcebergMigration {
+    let mut tables = Vec::new();
+    for entity in &schema.entities {
+        if let Some(table) = generate_iceberg_schema(entity) {
+            tables.push((entity.name.clone(), table));
+        }
+    }
+    IcebergMigration { tables }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::ast::*;
+
+    #[test]
+    fn test_to_iceberg_type_basic() {
+        assert_eq!(to_iceberg_type(&FieldType::UUID), "uuid");
+        assert_eq!(to_iceberg_type(&FieldType::Timestamp), "timestamptz");
+        assert_eq!(to_iceberg_type(&FieldType::Decimal), "decimal(38,9)");
+    }
+
+    #[test]
+    fn test_to_iceberg_type_vector_and_array() {
+        assert_eq!(to_iceberg_type(&FieldType::Vector(384)), "fixed[1536]");
+        assert_eq!(
+            to_iceberg_type(&FieldType::Array(Box::new(FieldType::Int))),
+            "list<long>"
+        );
+    }
+
+    #[test]
+    fn test_generate_iceberg_schema_only_olap_fields() {
+        let mut entity = Entity::new("Product".to_string());
+        entity.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        entity.add_field(Field {
+            name: "monthly_sales".to_string(),
+            field_type: FieldType::Decimal,
+            nullable: false, unique: false, primary_key: false,
+            default: None, backend: Some(BackendAnnotation::OLAP),
+        });
+
+        let table = generate_iceberg_schema(&entity).unwrap();
+        assert_eq!(table.fields.len(), 1);
+        assert_eq!(table.fields[0].name, "monthly_sales");
+        assert_eq!(table.fields[0].field_type, "decimal(38,9)");
+        assert_eq!(table.fields[0].id, 1);
+    }
+
+    #[test]
+    fn test_generate_iceberg_schema_none_without_olap_fields() {
+        let mut entity = Entity::new("User".to_string());
+        entity.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+
+        assert!(generate_iceberg_schema(&entity).is_none());
+    }
+}
@@ -1,7 +1,17 @@
+pub mod arrow;
+pub mod diff;
+pub mod emitter;
 pub mod generator;
+pub mod iceberg;
 pub mod type_map;
 
-pub use generator::{generate_migration, Migration, MigrationError};
+pub use diff::{generate_diff_migration, generate_migration_diff, RenameHint};
+pub use emitter::{
+    generate_backend_ddl, BackendEmitter, OlapEmitter, PostgresEmitter, VectorDistance,
+    VectorEmitter, VectorIndexKind,
+};
+pub use generator::{as_of_query, generate_migration, generate_upsert, generate_upsert_with_mode, Migration, MigrationError, UpsertMode};
+pub use iceberg::{generate_iceberg_migration, generate_iceberg_schema, IcebergMigration, IcebergTable};
 
 #[cfg(test)]
 mod tests {
@@ -276,6 +286,26 @@ mod tests {
         assert!(order_pos < item_pos, "Order must be created before OrderItem");
     }
 
+    // ─── REVERSIBLE MIGRATIONS ───
+
+    #[test]
+    fn test_down_migration_drops_in_reverse_creation_order() {
+        let schema = test_schema();
+        let migration = generate_migration(&schema).unwrap();
+
+        assert!(migration.down_sql.contains("DROP TABLE IF EXISTS users CASCADE;"));
+        assert!(migration.down_sql.contains("DROP TABLE IF EXISTS orders CASCADE;"));
+        assert!(migration.down_sql.contains("DROP TABLE IF EXISTS order_items CASCADE;"));
+
+        // OrderItem depends on Order depends on User, so rolling back must
+        // drop in the opposite order it was created in.
+        let item_pos = migration.down_sql.find("DROP TABLE IF EXISTS order_items").unwrap();
+        let order_pos = migration.down_sql.find("DROP TABLE IF EXISTS orders").unwrap();
+        let user_pos = migration.down_sql.find("DROP TABLE IF EXISTS users").unwrap();
+        assert!(item_pos < order_pos, "OrderItem must be dropped before Order");
+        assert!(order_pos < user_pos, "Order must be dropped before User");
+    }
+
     // ─── FULL SCHEMA ───
 
     #[test]
@@ -327,4 +357,272 @@ mod tests {
         assert!(migration.sql.contains("views INTEGER NOT NULL"));
         assert!(migration.sql.contains("sales NUMERIC NOT NULL"));
     }
+
+    // ─── MANY-TO-MANY JOIN TABLES ───
+
+    #[test]
+    fn test_many_to_many_join_table() {
+        let mut schema = Schema::new();
+
+        let mut user = Entity::new("User".to_string());
+        user.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        user.add_relation(Relation {
+            name: "roles".to_string(),
+            kind: RelationKind::ManyToMany,
+            target_entity: "Role".to_string(),
+            foreign_key: None,
+            through: Some("user_roles".to_string()),
+        });
+        schema.add_entity(user);
+
+        let mut role = Entity::new("Role".to_string());
+        role.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        schema.add_entity(role);
+
+        let migration = generate_migration(&schema).unwrap();
+
+        assert!(migration.sql.contains("CREATE TABLE user_roles"));
+        assert!(migration.sql.contains("user_id UUID NOT NULL"));
+        assert!(migration.sql.contains("role_id UUID NOT NULL"));
+        assert!(migration.sql.contains("PRIMARY KEY (user_id, role_id)"));
+        assert!(migration.sql.contains("FOREIGN KEY (user_id) REFERENCES users(id)"));
+        assert!(migration.sql.contains("FOREIGN KEY (role_id) REFERENCES roles(id)"));
+
+        // The join table must come after both endpoint tables
+        let user_pos = migration.sql.find("CREATE TABLE users").unwrap();
+        let role_pos = migration.sql.find("CREATE TABLE roles").unwrap();
+        let join_pos = migration.sql.find("CREATE TABLE user_roles").unwrap();
+        assert!(user_pos < join_pos);
+        assert!(role_pos < join_pos);
+    }
+
+    // ─── NAMESPACED ENTITIES ───
+
+    #[test]
+    fn test_namespaced_entity_emits_qualified_table_name() {
+        let mut schema = Schema::new();
+        let mut order = Entity::from_qualified_name("analytics.Order");
+        order.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        schema.add_entity(order);
+
+        let migration = generate_migration(&schema).unwrap();
+        assert!(migration.sql.contains("CREATE TABLE analytics.orders"));
+    }
+
+    // ─── BITEMPORAL ENTITIES ───
+
+    #[test]
+    fn test_temporal_entity_gets_validity_range_and_history_table() {
+        let mut schema = Schema::new();
+        let mut order = Entity::new("Order".to_string());
+        order.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        order.temporal = true;
+        schema.add_entity(order);
+
+        let migration = generate_migration(&schema).unwrap();
+
+        assert!(migration.sql.contains("valid_from TIMESTAMPTZ NOT NULL DEFAULT NOW()"));
+        assert!(migration.sql.contains("valid_to TIMESTAMPTZ"));
+        assert!(migration.sql.contains("CREATE TABLE orders_history"));
+        assert!(migration.sql.contains("tx_id BIGINT NOT NULL"));
+        assert!(migration.sql.contains("txid_current()"));
+        assert!(migration.sql.contains("CREATE TRIGGER orders_versioning"));
+    }
+
+    #[test]
+    fn test_temporal_trigger_asserts_new_version_and_retracts_old() {
+        let mut schema = Schema::new();
+        let mut order = Entity::new("Order".to_string());
+        order.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        order.temporal = true;
+        schema.add_entity(order);
+
+        let migration = generate_migration(&schema).unwrap();
+
+        assert!(migration.sql.contains("op VARCHAR NOT NULL"));
+        assert!(migration.sql.contains("'retract'"));
+        assert!(migration.sql.contains("IF TG_OP = 'UPDATE' THEN"));
+        assert!(migration.sql.contains("'assert'"));
+        assert!(migration.sql.contains("CREATE FUNCTION orders_as_of(at_timestamp TIMESTAMPTZ) RETURNS SETOF orders"));
+    }
+
+    #[test]
+    fn test_history_annotated_field_alone_triggers_bitemporal_support() {
+        let mut schema = Schema::new();
+        let mut log = Entity::new("AuditLog".to_string());
+        log.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        log.add_field(Field {
+            name: "note".to_string(),
+            field_type: FieldType::String,
+            nullable: false, unique: false, primary_key: false,
+            default: None, backend: Some(BackendAnnotation::History),
+        });
+        schema.add_entity(log);
+
+        let migration = generate_migration(&schema).unwrap();
+
+        assert!(migration.sql.contains("CREATE TABLE audit_logs_history"));
+        assert!(migration.sql.contains("valid_from TIMESTAMPTZ NOT NULL DEFAULT NOW()"));
+    }
+
+    // ─── UPSERT DML ───
+
+    #[test]
+    fn test_generate_upsert_keys_on_unique_field() {
+        let schema = test_schema();
+        let user = schema.get_entity("User").unwrap();
+
+        let upsert = generate_upsert(user).unwrap();
+
+        assert!(upsert.starts_with("INSERT INTO users ("));
+        assert!(upsert.contains("ON CONFLICT (email) DO UPDATE SET"));
+        assert!(upsert.contains("name = EXCLUDED.name"));
+        assert!(!upsert.contains("email = EXCLUDED.email"), "the conflict column isn't also an update target");
+    }
+
+    #[test]
+    fn test_generate_upsert_falls_back_to_primary_key() {
+        let mut schema = Schema::new();
+        let mut entity = Entity::new("Session".to_string());
+        entity.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        entity.add_field(Field {
+            name: "payload".to_string(),
+            field_type: FieldType::String,
+            nullable: false, unique: false, primary_key: false,
+            default: None, backend: None,
+        });
+        schema.add_entity(entity);
+
+        let upsert = generate_upsert(schema.get_entity("Session").unwrap()).unwrap();
+        assert!(upsert.contains("ON CONFLICT (id) DO UPDATE SET payload = EXCLUDED.payload;"));
+    }
+
+    #[test]
+    fn test_generate_upsert_skips_non_oltp_fields() {
+        let mut schema = Schema::new();
+        let mut entity = Entity::new("Product".to_string());
+        entity.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        entity.add_field(Field {
+            name: "views".to_string(),
+            field_type: FieldType::Int,
+            nullable: false, unique: false, primary_key: false,
+            default: None, backend: Some(BackendAnnotation::Cache),
+        });
+        schema.add_entity(entity);
+
+        let upsert = generate_upsert(schema.get_entity("Product").unwrap()).unwrap();
+        assert!(!upsert.contains("views"));
+    }
+
+    #[test]
+    fn test_generate_upsert_rejects_multiple_unique_fields() {
+        let mut schema = Schema::new();
+        let mut entity = Entity::new("User".to_string());
+        entity.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        entity.add_field(Field {
+            name: "email".to_string(),
+            field_type: FieldType::String,
+            nullable: false, unique: true, primary_key: false,
+            default: None, backend: None,
+        });
+        entity.add_field(Field {
+            name: "username".to_string(),
+            field_type: FieldType::String,
+            nullable: false, unique: true, primary_key: false,
+            default: None, backend: None,
+        });
+        schema.add_entity(entity);
+
+        let result = generate_upsert(schema.get_entity("User").unwrap());
+        assert!(matches!(result, Err(MigrationError::AmbiguousConflictTarget(_))));
+    }
+
+    #[test]
+    fn test_generate_upsert_replace_mode_updates_only_named_columns() {
+        let mut schema = Schema::new();
+        let mut entity = Entity::new("Session".to_string());
+        entity.add_field(Field {
+            name: "id".to_string(),
+            field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        entity.add_field(Field {
+            name: "payload".to_string(),
+            field_type: FieldType::String,
+            nullable: false, unique: false, primary_key: false,
+            default: None, backend: None,
+        });
+        entity.add_field(Field {
+            name: "last_seen_at".to_string(),
+            field_type: FieldType::Timestamp,
+            nullable: false, unique: false, primary_key: false,
+            default: None, backend: None,
+        });
+        schema.add_entity(entity);
+
+        let upsert = generate_upsert_with_mode(
+            schema.get_entity("Session").unwrap(),
+            &UpsertMode::Replace(vec!["last_seen_at".to_string()]),
+        ).unwrap();
+
+        assert!(upsert.contains("DO UPDATE SET last_seen_at = EXCLUDED.last_seen_at;"));
+        assert!(!upsert.contains("payload = EXCLUDED.payload"));
+    }
+
+    #[test]
+    fn test_as_of_query() {
+        let mut order = Entity::new("Order".to_string());
+        order.temporal = true;
+        let query = as_of_query(&order, "$1");
+
+        assert!(query.contains("FROM orders"));
+        assert!(query.contains("valid_from <= $1"));
+        assert!(query.contains("valid_to IS NULL OR valid_to > $1"));
+    }
 }
\ No newline at end of file
@@ -24,6 +24,55 @@ pub fn to_postgres_default(default: &DefaultValue) -> String {
     }
 }
 
+/// Inverse of `to_postgres_type`: maps an `information_schema`-reported SQL
+/// type back to a ChameleonDB `FieldType`, for introspection.
+///
+/// `udt_name` should be the raw Postgres type name (e.g. from
+/// `information_schema.columns.udt_name`), lowercase and without the
+/// leading `_` that Postgres uses for array element types — callers detect
+/// arrays separately via `data_type = 'ARRAY'` and recurse with the element
+/// type. `typmod` is the column's `pg_attribute.atttypmod`: Postgres never
+/// folds a type modifier like pgvector's dimension into `udt_name` itself
+/// (that's always just `"vector"`), so the dimension has to come from here.
+pub fn from_postgres_type(udt_name: &str, typmod: Option<i32>) -> FieldType {
+    if udt_name == "vector" {
+        if let Some(dim) = typmod {
+            if dim > 0 {
+                return FieldType::Vector(dim as usize);
+            }
+        }
+    }
+
+    match udt_name {
+        "uuid" => FieldType::UUID,
+        "varchar" | "text" | "bpchar" => FieldType::String,
+        "int2" | "int4" | "int8" => FieldType::Int,
+        "numeric" => FieldType::Decimal,
+        "bool" => FieldType::Bool,
+        "timestamp" | "timestamptz" => FieldType::Timestamp,
+        "float4" | "float8" => FieldType::Float,
+        // Unknown SQL types round-trip as opaque text rather than failing
+        // the whole introspection run.
+        _ => FieldType::String,
+    }
+}
+
+/// Inverse of `to_postgres_default`: best-effort recovery of a
+/// `DefaultValue` from the `column_default` expression Postgres reports.
+/// Anything that isn't recognizably `NOW()` or `gen_random_uuid()` is kept
+/// as a `Literal`, with any `::type` cast and surrounding single quotes
+/// stripped, so round-tripping never loses the default outright.
+pub fn from_postgres_default(expr: &str) -> DefaultValue {
+    let trimmed = expr.trim();
+    match trimmed.to_uppercase().as_str() {
+        "NOW()" | "CURRENT_TIMESTAMP" => DefaultValue::Now,
+        "GEN_RANDOM_UUID()" | "UUID_GENERATE_V4()" => DefaultValue::UUIDv4,
+        _ => DefaultValue::Literal(
+            trimmed.split("::").next().unwrap_or(trimmed).trim_matches('\'').to_string()
+        ),
+    }
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -64,4 +113,38 @@ mod tests {
         assert_eq!(to_postgres_default(&DefaultValue::UUIDv4), "gen_random_uuid()");
         assert_eq!(to_postgres_default(&DefaultValue::Literal("hello".to_string())), "'hello'");
     }
+
+    #[test]
+    fn test_from_postgres_type_basic() {
+        assert_eq!(from_postgres_type("uuid", None), FieldType::UUID);
+        assert_eq!(from_postgres_type("varchar", None), FieldType::String);
+        assert_eq!(from_postgres_type("int4", None), FieldType::Int);
+        assert_eq!(from_postgres_type("numeric", None), FieldType::Decimal);
+        assert_eq!(from_postgres_type("bool", None), FieldType::Bool);
+        assert_eq!(from_postgres_type("timestamptz", None), FieldType::Timestamp);
+        assert_eq!(from_postgres_type("float8", None), FieldType::Float);
+    }
+
+    #[test]
+    fn test_from_postgres_type_vector() {
+        // Postgres never embeds a pgvector column's dimension in `udt_name`
+        // (always plain `"vector"`) — it's carried separately as the
+        // column's `atttypmod`, which is what this asserts against.
+        assert_eq!(from_postgres_type("vector", Some(384)), FieldType::Vector(384));
+    }
+
+    #[test]
+    fn test_from_postgres_type_vector_without_typmod_falls_back_to_string() {
+        assert_eq!(from_postgres_type("vector", None), FieldType::String);
+    }
+
+    #[test]
+    fn test_from_postgres_default() {
+        assert_eq!(from_postgres_default("now()"), DefaultValue::Now);
+        assert_eq!(from_postgres_default("gen_random_uuid()"), DefaultValue::UUIDv4);
+        assert_eq!(
+            from_postgres_default("'active'::character varying"),
+            DefaultValue::Literal("active".to_string())
+        );
+    }
 }
\ No newline at end of file
@@ -1,5 +1,12 @@
 use crate::ast::Schema;
 use crate::error::{ChameleonError, ParseErrorDetail};
+use crate::suggest::suggest;
+
+/// Fixed keyword set a mistyped token is checked against.
+const KEYWORDS: &[&str] = &[
+    "entity", "primary", "unique", "nullable", "via", "through",
+    "uuid", "string", "int", "decimal", "bool", "timestamp", "float", "vector",
+];
 
 // Incluir el módulo parser generado por lalrpop
 #[allow(clippy::all)]
@@ -106,34 +113,19 @@ fn enhance_parse_error(
 
 /// Add helpful suggestions based on error patterns
 fn add_suggestions(mut detail: ParseErrorDetail) -> ParseErrorDetail {
-    // Check for common typos in keywords
+    // Check for a mistyped keyword via edit distance, rather than a
+    // hardcoded list of known-bad spellings.
     if let Some(token) = &detail.token {
         let token_clean = token.replace("Token(", "").replace(")", "").replace("\"", "");
         let token_lower = token_clean.to_lowercase();
-        
-        // Typos in 'entity'
-        if token_lower.contains("entiy") 
-            || token_lower.contains("enity")
-            || token_lower.contains("entit")
-            || token_lower == "entiy" {
-            detail.suggestion = Some("Did you mean 'entity'?".to_string());
-        }
-        // Typos in 'primary'
-        else if token_lower.contains("primry") 
-            || token_lower.contains("pirmary")
-            || token_lower.contains("primari") {
-            detail.suggestion = Some("Did you mean 'primary'?".to_string());
-        }
-        // Typos in 'unique'
-        else if token_lower.contains("uniqu") && !token_lower.contains("unique") {
-            detail.suggestion = Some("Did you mean 'unique'?".to_string());
-        }
-        // Typos in 'nullable'
-        else if token_lower.contains("nullabe") || token_lower.contains("nulable") {
-            detail.suggestion = Some("Did you mean 'nullable'?".to_string());
+
+        if let Some(candidate) = suggest(&token_lower, KEYWORDS.iter().copied()) {
+            if candidate != token_lower {
+                detail.suggestion = Some(format!("Did you mean '{}'?", candidate));
+            }
         }
     }
-    
+
     // Check for common syntax mistakes based on message
     if detail.message.contains("expected one of") && detail.message.contains("\":\"") {
         if detail.suggestion.is_none() {
@@ -0,0 +1,423 @@
+use std::collections::{HashMap, HashSet};
+
+use crate::ast::{FieldType, Schema};
+use crate::suggest::suggest;
+use crate::typechecker::errors::TypeCheckError;
+
+use super::ast::{Clause, GroundClause, GroundValue, Literal, PredicateClause, PredicateOp, Query, Term, TriplePattern};
+
+/// The `sql::naming` entity → table conversion, matching the migration
+/// generator's convention (a plural, snake_case table name). Query tables
+/// are built over the OLTP DDL `generate_migration` emits, so they must
+/// agree on that mapping.
+fn entity_table(entity: &str) -> String {
+    crate::sql::naming::entity_to_table(entity)
+}
+
+/// The base (non-parameterized) field kinds a query variable can start out
+/// compatible with, before any binding site narrows it down. `Vector`/`Array`
+/// are left out — they're not meaningful predicate operands in this query
+/// layer.
+fn all_field_kinds() -> HashSet<FieldType> {
+    [
+        FieldType::UUID, FieldType::String, FieldType::Int,
+        FieldType::Decimal, FieldType::Bool, FieldType::Timestamp, FieldType::Float,
+    ].into_iter().collect()
+}
+
+/// The conjoining-clauses accumulator: the algebrized state of a query's
+/// conjunction, built up pattern by pattern the way Mentat's
+/// `ConjoiningClauses` is — one join per distinct entity variable, one
+/// value-type set per distinct data variable, and a growing list of
+/// cross-binding equalities and predicates.
+#[derive(Debug, Default)]
+pub struct ConjoiningClauses {
+    /// Each joined entity table, in join order: `(table_name, alias)`.
+    pub from: Vec<(String, String)>,
+    /// Every `(table_alias, column)` a variable is bound to, in the order
+    /// encountered.
+    pub column_bindings: HashMap<String, Vec<(String, String)>>,
+    /// Extra `WHERE` fragments: cross-binding equalities, predicates, and
+    /// literal equality constraints.
+    pub wheres: Vec<String>,
+    /// Each variable's remaining possible field types, narrowed by
+    /// intersection at every binding site.
+    pub value_types: HashMap<String, HashSet<FieldType>>,
+    /// Set once a clause proves the query can never match any rows, e.g. an
+    /// empty `ground` collection or relation.
+    pub known_empty: bool,
+
+    entity_vars: HashMap<String, (String, String)>, // var -> (entity, alias)
+    alias_counter: usize,
+    /// Constants pinned onto a variable by a `ground` scalar/tuple, so a
+    /// later pattern binding the same variable to a real column also gets
+    /// constrained against it.
+    ground_literals: HashMap<String, Literal>,
+}
+
+impl ConjoiningClauses {
+    fn fresh_alias(&mut self) -> String {
+        let alias = format!("t{}", self.alias_counter);
+        self.alias_counter += 1;
+        alias
+    }
+
+    /// Resolve (or create) the table join for an entity variable, erroring
+    /// if it was already bound to a different entity.
+    fn bind_entity_var(
+        &mut self,
+        var: &str,
+        entity: &str,
+        errors: &mut Vec<TypeCheckError>,
+    ) -> Option<String> {
+        if let Some((bound_entity, alias)) = self.entity_vars.get(var) {
+            if bound_entity != entity {
+                errors.push(TypeCheckError::QueryEntityVarConflict {
+                    var: var.to_string(),
+                    first_entity: bound_entity.clone(),
+                    second_entity: entity.to_string(),
+                });
+                return None;
+            }
+            return Some(alias.clone());
+        }
+
+        let alias = self.fresh_alias();
+        self.from.push((entity_table(entity), alias.clone()));
+        self.entity_vars.insert(var.to_string(), (entity.to_string(), alias.clone()));
+        Some(alias)
+    }
+
+    /// Record that `var` appears at `(alias, column)`, narrowing its
+    /// value-type set to `field_type` and emitting an equality constraint
+    /// against the first place it was already bound, if any.
+    fn bind_column(&mut self, var: &str, alias: &str, column: &str, field_type: &FieldType, errors: &mut Vec<TypeCheckError>) {
+        let entry = self.column_bindings.entry(var.to_string()).or_default();
+        let prior = entry.first().cloned();
+        entry.push((alias.to_string(), column.to_string()));
+
+        let types = self.value_types.entry(var.to_string())
+            .or_insert_with(all_field_kinds);
+        types.retain(|t| t == field_type);
+        if types.is_empty() {
+            errors.push(TypeCheckError::QueryTypeConflict { var: var.to_string() });
+        }
+
+        if let Some((prior_alias, prior_column)) = prior {
+            self.wheres.push(format!(
+                "{}.{} = {}.{}",
+                alias, column, prior_alias, prior_column
+            ));
+        } else if let Some(literal) = self.ground_literals.get(var).cloned() {
+            self.wheres.push(format!("{}.{} = {}", alias, column, literal.to_sql()));
+        }
+    }
+
+    /// The first `(alias, column)` a variable is bound to, for projecting
+    /// or referencing it in a predicate.
+    pub(crate) fn column_of(&self, var: &str) -> Option<(String, String)> {
+        self.column_bindings.get(var).and_then(|sites| sites.first().cloned())
+    }
+
+    /// Pin a `ground` scalar/tuple value onto `var`: narrow its value-type
+    /// set to the literal's type, equate it against any column it's
+    /// already bound to, and remember it for any binding that comes later.
+    fn pin_ground_value(&mut self, var: &str, literal: &Literal, errors: &mut Vec<TypeCheckError>) {
+        let field_type = literal.field_type();
+        let types = self.value_types.entry(var.to_string()).or_insert_with(all_field_kinds);
+        types.retain(|t| *t == field_type);
+        if types.is_empty() {
+            errors.push(TypeCheckError::QueryTypeConflict { var: var.to_string() });
+        }
+
+        if let Some((alias, column)) = self.column_of(var) {
+            self.wheres.push(format!("{}.{} = {}", alias, column, literal.to_sql()));
+        }
+
+        self.ground_literals.insert(var.to_string(), literal.clone());
+    }
+}
+
+/// Algebrize a triple pattern into `cc`, resolving its entity and field
+/// against `schema`.
+fn algebrize_pattern(
+    schema: &Schema,
+    cc: &mut ConjoiningClauses,
+    pattern: &TriplePattern,
+    errors: &mut Vec<TypeCheckError>,
+) {
+    let Some(entity) = schema.get_entity(&pattern.entity) else {
+        errors.push(TypeCheckError::UnknownQueryEntity { entity: pattern.entity.clone() });
+        return;
+    };
+
+    let Some(field) = entity.fields.get(&pattern.field) else {
+        let suggestion = suggest(&pattern.field, entity.fields.keys().map(|s| s.as_str()))
+            .map(|s| s.to_string());
+        errors.push(TypeCheckError::UnknownQueryField {
+            entity: pattern.entity.clone(),
+            field: pattern.field.clone(),
+            suggestion,
+        });
+        return;
+    };
+
+    let Some(alias) = cc.bind_entity_var(&pattern.entity_var, &pattern.entity, errors) else {
+        return;
+    };
+
+    match &pattern.term {
+        Term::Var(var) => cc.bind_column(var, &alias, &field.name, &field.field_type, errors),
+        Term::Const(literal) => {
+            cc.wheres.push(format!("{}.{} = {}", alias, field.name, literal.to_sql()));
+        }
+    }
+}
+
+/// Algebrize a predicate clause, validating both operands' inferred types
+/// against what the operator requires.
+fn algebrize_predicate(cc: &mut ConjoiningClauses, predicate: &PredicateClause, errors: &mut Vec<TypeCheckError>) {
+    let render = |term: &Term, cc: &ConjoiningClauses| -> Option<String> {
+        match term {
+            Term::Var(var) => cc.column_of(var).map(|(alias, column)| format!("{}.{}", alias, column)),
+            Term::Const(literal) => Some(literal.to_sql()),
+        }
+    };
+
+    if predicate.op.requires_ordered_type() {
+        let ordered = [FieldType::Int, FieldType::Decimal, FieldType::Float, FieldType::Timestamp];
+        for term in [&predicate.left, &predicate.right] {
+            if let Term::Var(var) = term {
+                let types = cc.value_types.entry(var.clone()).or_insert_with(all_field_kinds);
+                let narrowed: HashSet<FieldType> = types.iter()
+                    .filter(|t| ordered.contains(t))
+                    .cloned()
+                    .collect();
+                if narrowed.is_empty() {
+                    errors.push(TypeCheckError::QueryPredicateTypeMismatch {
+                        op: predicate.op.as_sql().to_string(),
+                        var: var.clone(),
+                        allowed: types.iter().map(|t| format!("{:?}", t)).collect(),
+                    });
+                } else {
+                    *types = narrowed;
+                }
+            }
+        }
+    }
+
+    if let (Some(left), Some(right)) = (render(&predicate.left, cc), render(&predicate.right, cc)) {
+        cc.wheres.push(format!("{} {} {}", left, predicate.op.as_sql(), right));
+    }
+}
+
+/// Algebrize a full conjunction (a query's top-level clauses, or the
+/// clauses inside a `not`/`or` branch) into `cc`.
+pub fn algebrize_clauses(
+    schema: &Schema,
+    cc: &mut ConjoiningClauses,
+    clauses: &[Clause],
+    errors: &mut Vec<TypeCheckError>,
+) {
+    for clause in clauses {
+        match clause {
+            Clause::Pattern(pattern) => algebrize_pattern(schema, cc, pattern, errors),
+            Clause::Predicate(predicate) => algebrize_predicate(cc, predicate, errors),
+            Clause::Not(inner) => algebrize_not(schema, cc, inner, errors),
+            Clause::Or(branches) => algebrize_or(schema, cc, branches, errors),
+            Clause::Ground(ground) => algebrize_ground(cc, ground, errors),
+        }
+    }
+}
+
+/// `ground`: bind literal data directly into the query's variables,
+/// dispatching on which of the four shapes it takes.
+fn algebrize_ground(cc: &mut ConjoiningClauses, ground: &GroundClause, errors: &mut Vec<TypeCheckError>) {
+    match &ground.value {
+        GroundValue::Scalar(literal) => {
+            if ground.vars.len() != 1 {
+                errors.push(TypeCheckError::GroundArityMismatch { expected: 1, found: ground.vars.len() });
+                return;
+            }
+            cc.pin_ground_value(&ground.vars[0], literal, errors);
+        }
+        GroundValue::Tuple(values) => {
+            if values.len() != ground.vars.len() {
+                errors.push(TypeCheckError::GroundArityMismatch { expected: ground.vars.len(), found: values.len() });
+                return;
+            }
+            for (var, literal) in ground.vars.iter().zip(values.iter()) {
+                cc.pin_ground_value(var, literal, errors);
+            }
+        }
+        GroundValue::Collection(values) => {
+            if ground.vars.len() != 1 {
+                errors.push(TypeCheckError::GroundArityMismatch { expected: 1, found: ground.vars.len() });
+                return;
+            }
+            algebrize_ground_collection(cc, &ground.vars[0], values, errors);
+        }
+        GroundValue::Relation(rows) => algebrize_ground_relation(cc, &ground.vars, rows, errors),
+    }
+}
+
+/// `[?x ...]`: a single column of values. An empty collection makes the
+/// whole query provably empty. When `var` already joins to a real column,
+/// this narrows to a plain `IN (...)`; otherwise it's introduced as its
+/// own `VALUES` derived table, the same way a relation is.
+fn algebrize_ground_collection(cc: &mut ConjoiningClauses, var: &str, values: &[Literal], errors: &mut Vec<TypeCheckError>) {
+    if values.is_empty() {
+        cc.known_empty = true;
+        return;
+    }
+
+    let field_type = values[0].field_type();
+    if values.iter().any(|v| v.field_type() != field_type) {
+        errors.push(TypeCheckError::GroundValuesNotUniform { var: var.to_string() });
+        return;
+    }
+
+    if let Some((alias, column)) = cc.column_of(var) {
+        let types = cc.value_types.entry(var.to_string()).or_insert_with(all_field_kinds);
+        types.retain(|t| *t == field_type);
+        if types.is_empty() {
+            errors.push(TypeCheckError::QueryTypeConflict { var: var.to_string() });
+        }
+
+        let list = values.iter().map(Literal::to_sql).collect::<Vec<String>>().join(", ");
+        cc.wheres.push(format!("{}.{} IN ({})", alias, column, list));
+    } else {
+        let rows = values.iter().map(|v| format!("({})", v.to_sql())).collect::<Vec<String>>().join(", ");
+        let alias = cc.fresh_alias();
+        cc.from.push((format!("(VALUES {})", rows), format!("{}(v)", alias)));
+        cc.bind_column(var, &alias, "v", &field_type, errors);
+    }
+}
+
+/// `[[?a ?b]]`: a rectangular table of rows, joined in as a `VALUES`
+/// derived table aliased over `vars`. An empty relation makes the whole
+/// query provably empty; a ragged one is an arity error.
+fn algebrize_ground_relation(cc: &mut ConjoiningClauses, vars: &[String], rows: &[Vec<Literal>], errors: &mut Vec<TypeCheckError>) {
+    if rows.is_empty() {
+        cc.known_empty = true;
+        return;
+    }
+
+    for row in rows {
+        if row.len() != vars.len() {
+            errors.push(TypeCheckError::GroundArityMismatch { expected: vars.len(), found: row.len() });
+            return;
+        }
+    }
+
+    let mut column_types = Vec::with_capacity(vars.len());
+    for (col, var) in vars.iter().enumerate() {
+        let field_type = rows[0][col].field_type();
+        if rows.iter().any(|row| row[col].field_type() != field_type) {
+            errors.push(TypeCheckError::GroundValuesNotUniform { var: var.clone() });
+            return;
+        }
+        column_types.push(field_type);
+    }
+
+    let columns: Vec<String> = vars.iter().map(|v| v.trim_start_matches('?').to_string()).collect();
+    let values_sql = rows.iter()
+        .map(|row| format!("({})", row.iter().map(Literal::to_sql).collect::<Vec<String>>().join(", ")))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let alias = cc.fresh_alias();
+    cc.from.push((format!("(VALUES {})", values_sql), format!("{}({})", alias, columns.join(", "))));
+
+    for ((var, column), field_type) in vars.iter().zip(columns.iter()).zip(column_types.iter()) {
+        cc.bind_column(var, &alias, column, field_type, errors);
+    }
+}
+
+/// `not`: the inner clauses algebrize into their own sub-`ConjoiningClauses`
+/// (so they can introduce their own joined tables), correlated back to the
+/// outer query through any variables already bound there, then compile to a
+/// single `NOT EXISTS (...)` fragment in the outer `wheres`.
+fn algebrize_not(schema: &Schema, cc: &mut ConjoiningClauses, inner: &[Clause], errors: &mut Vec<TypeCheckError>) {
+    let mut inner_cc = ConjoiningClauses::default();
+    // Share the alias counter so the subquery's table aliases can never
+    // collide with an outer alias it correlates against.
+    inner_cc.alias_counter = cc.alias_counter;
+    algebrize_clauses(schema, &mut inner_cc, inner, errors);
+    cc.alias_counter = inner_cc.alias_counter;
+
+    let mut correlation = Vec::new();
+    for (var, sites) in &inner_cc.column_bindings {
+        if let Some((outer_alias, outer_column)) = cc.column_of(var) {
+            if let Some((inner_alias, inner_column)) = sites.first() {
+                correlation.push(format!("{}.{} = {}.{}", inner_alias, inner_column, outer_alias, outer_column));
+            }
+        }
+    }
+
+    let mut conditions = inner_cc.wheres.clone();
+    conditions.extend(correlation);
+
+    let from_clause = inner_cc.from.iter()
+        .map(|(table, alias)| format!("{} {}", table, alias))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let where_clause = if conditions.is_empty() {
+        String::new()
+    } else {
+        format!(" WHERE {}", conditions.join(" AND "))
+    };
+
+    cc.wheres.push(format!("NOT EXISTS (SELECT 1 FROM {}{})", from_clause, where_clause));
+}
+
+/// `or`: each branch is a conjunction over variables already bound in the
+/// outer query — a branch can't introduce a new joined table, since every
+/// row of the outer query must be able to evaluate every branch, and a
+/// table joined inside just one branch would have no FROM/JOIN entry
+/// outside it. Enforced as `OrBranchIntroducesEntityVar`. Each branch
+/// compiles to its own parenthesized `AND`-conjunction; the branches are
+/// joined with `OR`.
+fn algebrize_or(schema: &Schema, cc: &mut ConjoiningClauses, branches: &[Vec<Clause>], errors: &mut Vec<TypeCheckError>) {
+    let mut branch_wheres = Vec::new();
+
+    for branch in branches {
+        let mut branch_cc = ConjoiningClauses::default();
+        branch_cc.entity_vars = cc.entity_vars.clone();
+        branch_cc.value_types = cc.value_types.clone();
+        branch_cc.column_bindings = cc.column_bindings.clone();
+        branch_cc.ground_literals = cc.ground_literals.clone();
+
+        algebrize_clauses(schema, &mut branch_cc, branch, errors);
+
+        for (var, (entity, _)) in &branch_cc.entity_vars {
+            if !cc.entity_vars.contains_key(var) {
+                errors.push(TypeCheckError::OrBranchIntroducesEntityVar {
+                    var: var.clone(),
+                    entity: entity.clone(),
+                });
+            }
+        }
+
+        if branch_cc.wheres.is_empty() {
+            branch_wheres.push("TRUE".to_string());
+        } else {
+            branch_wheres.push(format!("({})", branch_cc.wheres.join(" AND ")));
+        }
+
+        // Narrowing performed inside a disjunctive branch only holds when
+        // that branch is taken, so it can't be promoted to the outer `cc`.
+    }
+
+    cc.wheres.push(format!("({})", branch_wheres.join(" OR ")));
+}
+
+/// Algebrize a full query, returning the accumulated `ConjoiningClauses`
+/// alongside any type errors found along the way.
+pub fn algebrize(schema: &Schema, query: &Query) -> (ConjoiningClauses, Vec<TypeCheckError>) {
+    let mut cc = ConjoiningClauses::default();
+    let mut errors = Vec::new();
+    algebrize_clauses(schema, &mut cc, &query.clauses, &mut errors);
+    (cc, errors)
+}
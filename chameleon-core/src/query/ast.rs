@@ -0,0 +1,143 @@
+use crate::ast::FieldType;
+
+/// A query-side literal value. Each variant maps onto exactly one
+/// `FieldType`, which is how a `ground` value narrows the value-type set
+/// of the variable it binds.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Literal {
+    Int(i64),
+    Decimal(String),
+    Float(f64),
+    Bool(bool),
+    String(String),
+    Timestamp(String),
+    UUID(String),
+}
+
+impl Literal {
+    /// The `FieldType` this literal is shaped like.
+    pub fn field_type(&self) -> FieldType {
+        match self {
+            Literal::Int(_) => FieldType::Int,
+            Literal::Decimal(_) => FieldType::Decimal,
+            Literal::Float(_) => FieldType::Float,
+            Literal::Bool(_) => FieldType::Bool,
+            Literal::String(_) => FieldType::String,
+            Literal::Timestamp(_) => FieldType::Timestamp,
+            Literal::UUID(_) => FieldType::UUID,
+        }
+    }
+
+    /// Render as a SQL literal, quoting and escaping text-like values.
+    pub fn to_sql(&self) -> String {
+        match self {
+            Literal::Int(v) => v.to_string(),
+            Literal::Decimal(v) => v.clone(),
+            Literal::Float(v) => v.to_string(),
+            Literal::Bool(v) => v.to_string(),
+            Literal::String(v) => format!("'{}'", v.replace('\'', "''")),
+            Literal::Timestamp(v) => format!("'{}'", v.replace('\'', "''")),
+            Literal::UUID(v) => format!("'{}'", v.replace('\'', "''")),
+        }
+    }
+}
+
+/// A query-side term: either a bound variable (`?var`) or a literal.
+#[derive(Debug, Clone, PartialEq)]
+pub enum Term {
+    Var(String),
+    Const(Literal),
+}
+
+/// A triple pattern `[?entity_var :field term]`: `entity_var` names a row
+/// of `entity`, and `term` is either the variable that row's `field`
+/// resolves to, or a literal it must equal.
+#[derive(Debug, Clone)]
+pub struct TriplePattern {
+    pub entity_var: String,
+    pub entity: String,
+    pub field: String,
+    pub term: Term,
+}
+
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum PredicateOp {
+    Gt,
+    Lt,
+    Gte,
+    Lte,
+    Eq,
+    Ne,
+}
+
+impl PredicateOp {
+    pub fn as_sql(&self) -> &'static str {
+        match self {
+            PredicateOp::Gt => ">",
+            PredicateOp::Lt => "<",
+            PredicateOp::Gte => ">=",
+            PredicateOp::Lte => "<=",
+            PredicateOp::Eq => "=",
+            PredicateOp::Ne => "<>",
+        }
+    }
+
+    /// Ordering comparisons only make sense on types with a natural order;
+    /// `=`/`<>` apply to anything.
+    pub fn requires_ordered_type(&self) -> bool {
+        matches!(self, PredicateOp::Gt | PredicateOp::Lt | PredicateOp::Gte | PredicateOp::Lte)
+    }
+}
+
+/// A predicate clause `[(op term term)]`.
+#[derive(Debug, Clone)]
+pub struct PredicateClause {
+    pub op: PredicateOp,
+    pub left: Term,
+    pub right: Term,
+}
+
+/// The value shape bound into a `ground` clause, mirroring Mentat's
+/// scalar/tuple/coll/rel distinction.
+#[derive(Debug, Clone)]
+pub enum GroundValue {
+    /// `?x` ← one value.
+    Scalar(Literal),
+    /// `[?a ?b]` ← one row; its length must match the clause's `vars`.
+    Tuple(Vec<Literal>),
+    /// `[?x ...]` ← one column of N values.
+    Collection(Vec<Literal>),
+    /// `[[?a ?b]]` ← a rectangular table of rows, each matching `vars`.
+    Relation(Vec<Vec<Literal>>),
+}
+
+/// `ground`: bind constant data directly into a query's variables, rather
+/// than deriving it from a pattern over the schema.
+#[derive(Debug, Clone)]
+pub struct GroundClause {
+    pub vars: Vec<String>,
+    pub value: GroundValue,
+}
+
+/// A single clause in a query's conjunction.
+#[derive(Debug, Clone)]
+pub enum Clause {
+    Pattern(TriplePattern),
+    Predicate(PredicateClause),
+    /// `not`: none of the inner clauses may hold, compiled as `NOT EXISTS`.
+    Not(Vec<Clause>),
+    /// `or`: at least one branch (itself a conjunction of clauses) must
+    /// hold. Branches may only reference variables already bound outside
+    /// the `or` — they can't introduce new joined tables of their own.
+    Or(Vec<Vec<Clause>>),
+    /// `ground`: bind literal data directly into the query's variables.
+    Ground(GroundClause),
+}
+
+/// A full query: the variables to project (`find`) and the clauses that
+/// constrain them.
+#[derive(Debug, Clone, Default)]
+pub struct Query {
+    pub find: Vec<String>,
+    pub clauses: Vec<Clause>,
+}
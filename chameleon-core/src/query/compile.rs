@@ -0,0 +1,34 @@
+use super::algebrize::ConjoiningClauses;
+use super::ast::Query;
+
+/// Render the fully algebrized `cc` into a `SELECT` statement projecting
+/// `query.find`, or `None` if a ground clause already proved the query
+/// empty.
+pub fn compile_sql(cc: &ConjoiningClauses, query: &Query) -> Option<String> {
+    if cc.known_empty {
+        return None;
+    }
+
+    let projection: Vec<String> = query.find.iter()
+        .filter_map(|var| {
+            cc.column_of(var).map(|(alias, column)| {
+                format!("{}.{} AS {}", alias, column, var.trim_start_matches('?'))
+            })
+        })
+        .collect();
+
+    let select = if projection.is_empty() { "*".to_string() } else { projection.join(", ") };
+
+    let from = cc.from.iter()
+        .map(|(table, alias)| format!("{} {}", table, alias))
+        .collect::<Vec<String>>()
+        .join(", ");
+
+    let mut sql = format!("SELECT {} FROM {}", select, from);
+    if !cc.wheres.is_empty() {
+        sql.push_str(&format!(" WHERE {}", cc.wheres.join(" AND ")));
+    }
+    sql.push(';');
+
+    Some(sql)
+}
@@ -0,0 +1,365 @@
+pub mod algebrize;
+pub mod ast;
+mod compile;
+
+#[cfg(test)]
+mod test_helper;
+
+use crate::ast::Schema;
+use crate::typechecker::TypeCheckResult;
+
+pub use ast::{Clause, GroundClause, GroundValue, Literal, PredicateClause, PredicateOp, Query, Term, TriplePattern};
+
+/// The outcome of compiling a `Query` against a `Schema`: the generated SQL
+/// (`None` if type errors blocked compilation, or if a ground clause proved
+/// the query empty) and a `TypeCheckResult` so callers reuse the same
+/// `is_valid()`/`error_report()` pipeline as schema type-checking.
+#[derive(Debug)]
+pub struct QueryResult {
+    pub sql: Option<String>,
+    pub type_check: TypeCheckResult,
+}
+
+impl QueryResult {
+    pub fn is_valid(&self) -> bool {
+        self.type_check.is_valid()
+    }
+
+    pub fn error_report(&self) -> String {
+        self.type_check.error_report()
+    }
+}
+
+/// Type-check `query` against `schema` and compile it to a PostgreSQL
+/// `SELECT`. SQL is only produced when the query type-checks cleanly.
+pub fn compile_query(schema: &Schema, query: &Query) -> QueryResult {
+    let (cc, errors) = algebrize::algebrize(schema, query);
+
+    let sql = if errors.is_empty() {
+        compile::compile_sql(&cc, query)
+    } else {
+        None
+    };
+
+    QueryResult {
+        sql,
+        type_check: TypeCheckResult { errors },
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use crate::query::test_helper::test_schema;
+
+    fn pattern(entity_var: &str, entity: &str, field: &str, term: Term) -> Clause {
+        Clause::Pattern(TriplePattern {
+            entity_var: entity_var.to_string(),
+            entity: entity.to_string(),
+            field: field.to_string(),
+            term,
+        })
+    }
+
+    #[test]
+    fn test_simple_pattern_compiles_to_select() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?name".to_string()],
+            clauses: vec![pattern("?u", "User", "name", Term::Var("?name".to_string()))],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(result.is_valid(), "{}", result.error_report());
+        let sql = result.sql.unwrap();
+        assert!(sql.starts_with("SELECT t0.name AS name FROM users t0"));
+    }
+
+    #[test]
+    fn test_shared_variable_joins_two_patterns() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?total".to_string()],
+            clauses: vec![
+                pattern("?u", "User", "id", Term::Var("?uid".to_string())),
+                pattern("?o", "Order", "user_id", Term::Var("?uid".to_string())),
+                pattern("?o", "Order", "total", Term::Var("?total".to_string())),
+            ],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(result.is_valid(), "{}", result.error_report());
+        let sql = result.sql.unwrap();
+        assert!(sql.contains("FROM users t0, orders t1"));
+        assert!(sql.contains("t1.user_id = t0.id"));
+    }
+
+    #[test]
+    fn test_unknown_field_is_reported() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?x".to_string()],
+            clauses: vec![pattern("?u", "User", "nickname", Term::Var("?x".to_string()))],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(!result.is_valid());
+        assert!(result.sql.is_none());
+        assert!(result.type_check.errors.iter().any(|e| matches!(e, crate::typechecker::errors::TypeCheckError::UnknownQueryField { .. })));
+    }
+
+    #[test]
+    fn test_conflicting_field_types_on_shared_variable() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?x".to_string()],
+            clauses: vec![
+                pattern("?u", "User", "name", Term::Var("?x".to_string())),
+                pattern("?o", "Order", "total", Term::Var("?x".to_string())),
+            ],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(!result.is_valid());
+        assert!(result.type_check.errors.iter().any(|e| matches!(e, crate::typechecker::errors::TypeCheckError::QueryTypeConflict { .. })));
+    }
+
+    #[test]
+    fn test_predicate_requires_ordered_type() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?name".to_string()],
+            clauses: vec![
+                pattern("?u", "User", "name", Term::Var("?name".to_string())),
+                Clause::Predicate(PredicateClause {
+                    op: PredicateOp::Gt,
+                    left: Term::Var("?name".to_string()),
+                    right: Term::Const(Literal::Int(100)),
+                }),
+            ],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(!result.is_valid());
+        assert!(result.type_check.errors.iter().any(|e| matches!(e, crate::typechecker::errors::TypeCheckError::QueryPredicateTypeMismatch { .. })));
+    }
+
+    #[test]
+    fn test_valid_predicate_compiles() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?total".to_string()],
+            clauses: vec![
+                pattern("?o", "Order", "total", Term::Var("?total".to_string())),
+                Clause::Predicate(PredicateClause {
+                    op: PredicateOp::Gt,
+                    left: Term::Var("?total".to_string()),
+                    right: Term::Const(Literal::Decimal("100".to_string())),
+                }),
+            ],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(result.is_valid(), "{}", result.error_report());
+        assert!(result.sql.unwrap().contains("t0.total > 100"));
+    }
+
+    #[test]
+    fn test_not_compiles_to_not_exists() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?uid".to_string()],
+            clauses: vec![
+                pattern("?u", "User", "id", Term::Var("?uid".to_string())),
+                Clause::Not(vec![
+                    pattern("?o", "Order", "user_id", Term::Var("?uid".to_string())),
+                ]),
+            ],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(result.is_valid(), "{}", result.error_report());
+        let sql = result.sql.unwrap();
+        assert!(sql.contains("NOT EXISTS (SELECT 1 FROM orders"));
+        assert!(sql.contains("user_id = t0.id"));
+    }
+
+    #[test]
+    fn test_or_compiles_to_disjunction() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?status".to_string()],
+            clauses: vec![
+                pattern("?o", "Order", "status", Term::Var("?status".to_string())),
+                Clause::Or(vec![
+                    vec![Clause::Predicate(PredicateClause {
+                        op: PredicateOp::Eq,
+                        left: Term::Var("?status".to_string()),
+                        right: Term::Const(Literal::String("paid".to_string())),
+                    })],
+                    vec![Clause::Predicate(PredicateClause {
+                        op: PredicateOp::Eq,
+                        left: Term::Var("?status".to_string()),
+                        right: Term::Const(Literal::String("shipped".to_string())),
+                    })],
+                ]),
+            ],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(result.is_valid(), "{}", result.error_report());
+        let sql = result.sql.unwrap();
+        assert!(sql.contains("'paid'"));
+        assert!(sql.contains("'shipped'"));
+        assert!(sql.contains(" OR "));
+    }
+
+    #[test]
+    fn test_or_branch_introducing_new_entity_var_is_rejected() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?status".to_string()],
+            clauses: vec![
+                pattern("?o", "Order", "status", Term::Var("?status".to_string())),
+                Clause::Or(vec![
+                    vec![Clause::Predicate(PredicateClause {
+                        op: PredicateOp::Eq,
+                        left: Term::Var("?status".to_string()),
+                        right: Term::Const(Literal::String("paid".to_string())),
+                    })],
+                    vec![pattern("?u", "User", "id", Term::Var("?status".to_string()))],
+                ]),
+            ],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(!result.is_valid());
+        assert!(result.sql.is_none());
+        assert!(result.type_check.errors.iter().any(|e| matches!(e, crate::typechecker::errors::TypeCheckError::OrBranchIntroducesEntityVar { .. })));
+    }
+
+    fn ground(vars: &[&str], value: GroundValue) -> Clause {
+        Clause::Ground(GroundClause {
+            vars: vars.iter().map(|v| v.to_string()).collect(),
+            value,
+        })
+    }
+
+    #[test]
+    fn test_ground_scalar_pins_column_value() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?name".to_string()],
+            clauses: vec![
+                pattern("?u", "User", "name", Term::Var("?name".to_string())),
+                ground(&["?name"], GroundValue::Scalar(Literal::String("Alice".to_string()))),
+            ],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(result.is_valid(), "{}", result.error_report());
+        assert!(result.sql.unwrap().contains("t0.name = 'Alice'"));
+    }
+
+    #[test]
+    fn test_ground_collection_narrows_to_in_clause() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?status".to_string()],
+            clauses: vec![
+                pattern("?o", "Order", "status", Term::Var("?status".to_string())),
+                ground(&["?status"], GroundValue::Collection(vec![
+                    Literal::String("paid".to_string()),
+                    Literal::String("shipped".to_string()),
+                ])),
+            ],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(result.is_valid(), "{}", result.error_report());
+        assert!(result.sql.unwrap().contains("t0.status IN ('paid', 'shipped')"));
+    }
+
+    #[test]
+    fn test_standalone_ground_collection_becomes_values_table() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?x".to_string()],
+            clauses: vec![ground(&["?x"], GroundValue::Collection(vec![
+                Literal::Int(1),
+                Literal::Int(2),
+                Literal::Int(3),
+            ]))],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(result.is_valid(), "{}", result.error_report());
+        let sql = result.sql.unwrap();
+        assert!(sql.contains("FROM (VALUES (1), (2), (3)) t0(v)"));
+        assert!(sql.contains("t0.v AS x"));
+    }
+
+    #[test]
+    fn test_empty_ground_collection_marks_query_empty() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?x".to_string()],
+            clauses: vec![ground(&["?x"], GroundValue::Collection(vec![]))],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(result.is_valid(), "{}", result.error_report());
+        assert!(result.sql.is_none());
+    }
+
+    #[test]
+    fn test_ground_relation_joins_on_bound_columns() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?total".to_string()],
+            clauses: vec![
+                pattern("?o", "Order", "status", Term::Var("?status".to_string())),
+                pattern("?o", "Order", "total", Term::Var("?total".to_string())),
+                ground(&["?status"], GroundValue::Relation(vec![
+                    vec![Literal::String("paid".to_string())],
+                    vec![Literal::String("shipped".to_string())],
+                ])),
+            ],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(result.is_valid(), "{}", result.error_report());
+        let sql = result.sql.unwrap();
+        assert!(sql.contains("(VALUES ('paid'), ('shipped')) t1(status)"));
+        assert!(sql.contains("t1.status = t0.status"));
+    }
+
+    #[test]
+    fn test_ground_tuple_arity_mismatch_is_rejected() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?x".to_string()],
+            clauses: vec![ground(&["?x", "?y"], GroundValue::Tuple(vec![Literal::Int(1)]))],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(!result.is_valid());
+        assert!(result.type_check.errors.iter().any(|e| matches!(e, crate::typechecker::errors::TypeCheckError::GroundArityMismatch { .. })));
+    }
+
+    #[test]
+    fn test_ground_collection_with_mixed_types_is_rejected() {
+        let schema = test_schema();
+        let query = Query {
+            find: vec!["?x".to_string()],
+            clauses: vec![ground(&["?x"], GroundValue::Collection(vec![
+                Literal::Int(1),
+                Literal::String("two".to_string()),
+            ]))],
+        };
+
+        let result = compile_query(&schema, &query);
+        assert!(!result.is_valid());
+        assert!(result.type_check.errors.iter().any(|e| matches!(e, crate::typechecker::errors::TypeCheckError::GroundValuesNotUniform { .. })));
+    }
+}
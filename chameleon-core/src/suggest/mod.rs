@@ -0,0 +1,85 @@
+//! Shared edit-distance "did you mean" helper, used by both the parser's
+//! error messages and the type checker's relation diagnostics.
+
+/// The maximum edit distance for a candidate to be worth suggesting at all.
+const MAX_SUGGESTION_DISTANCE: usize = 2;
+
+/// Standard two-row DP Levenshtein distance between `a` and `b`, O(n·m)
+/// time with only the previous and current rows kept in memory.
+pub fn levenshtein(a: &str, b: &str) -> usize {
+    let a: Vec<char> = a.chars().collect();
+    let b: Vec<char> = b.chars().collect();
+
+    let mut prev: Vec<usize> = (0..=b.len()).collect();
+    let mut curr: Vec<usize> = vec![0; b.len() + 1];
+
+    for i in 1..=a.len() {
+        curr[0] = i;
+        for j in 1..=b.len() {
+            let cost = if a[i - 1] == b[j - 1] { 0 } else { 1 };
+            curr[j] = (prev[j] + 1)
+                .min(curr[j - 1] + 1)
+                .min(prev[j - 1] + cost);
+        }
+        std::mem::swap(&mut prev, &mut curr);
+    }
+
+    prev[b.len()]
+}
+
+/// Find the candidate closest to `token` by edit distance, preferring the
+/// closest match. Returns `None` for an empty token or when every candidate
+/// is further than `MAX_SUGGESTION_DISTANCE` away.
+pub fn suggest<'a, I>(token: &str, candidates: I) -> Option<&'a str>
+where
+    I: IntoIterator<Item = &'a str>,
+{
+    if token.is_empty() {
+        return None;
+    }
+
+    candidates.into_iter()
+        .map(|candidate| (candidate, levenshtein(token, candidate)))
+        .filter(|(_, distance)| *distance <= MAX_SUGGESTION_DISTANCE)
+        .min_by_key(|(_, distance)| *distance)
+        .map(|(candidate, _)| candidate)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_levenshtein_identical() {
+        assert_eq!(levenshtein("entity", "entity"), 0);
+    }
+
+    #[test]
+    fn test_levenshtein_single_edit() {
+        assert_eq!(levenshtein("entiy", "entity"), 1);
+        assert_eq!(levenshtein("primry", "primary"), 1);
+    }
+
+    #[test]
+    fn test_levenshtein_empty_string() {
+        assert_eq!(levenshtein("", "entity"), 6);
+    }
+
+    #[test]
+    fn test_suggest_prefers_closest_candidate() {
+        let candidates = vec!["entity", "unique", "nullable"];
+        assert_eq!(suggest("entiy", candidates), Some("entity"));
+    }
+
+    #[test]
+    fn test_suggest_returns_none_beyond_threshold() {
+        let candidates = vec!["entity", "unique", "nullable"];
+        assert_eq!(suggest("xyzxyz", candidates), None);
+    }
+
+    #[test]
+    fn test_suggest_returns_none_for_empty_token() {
+        let candidates = vec!["entity"];
+        assert_eq!(suggest("", candidates), None);
+    }
+}
@@ -26,6 +26,125 @@ pub fn check_primary_keys(schema: &Schema) -> Vec<TypeCheckError> {
     errors
 }
 
+/// Flags entities with no primary key and no unique field: `generate_upsert`
+/// has no sound `ON CONFLICT` target for them. This is a warning, not a hard
+/// error — such an entity is still a valid append-only table, it just can't
+/// be upserted into.
+pub fn check_upsertability(schema: &Schema) -> Vec<TypeCheckError> {
+    let mut errors = Vec::new();
+
+    for entity in &schema.entities {
+        let has_conflict_target = entity.fields.values().any(|field| field.primary_key || field.unique);
+        if !has_conflict_target {
+            errors.push(TypeCheckError::NonUpsertableEntity {
+                entity: entity.name.clone(),
+            });
+        }
+    }
+
+    errors
+}
+
+/// Validates that bitemporal entities (`@temporal`, or carrying an
+/// `@history` field) don't shadow the generated `valid_from`/`valid_to`
+/// validity-range columns with their own fields.
+pub fn check_temporal_columns(schema: &Schema) -> Vec<TypeCheckError> {
+    let mut errors = Vec::new();
+
+    for entity in &schema.entities {
+        if !entity.is_bitemporal() {
+            continue;
+        }
+        for reserved in ["valid_from", "valid_to"] {
+            if entity.fields.contains_key(reserved) {
+                errors.push(TypeCheckError::TemporalColumnReserved {
+                    entity: entity.name.clone(),
+                    field: reserved.to_string(),
+                });
+            }
+        }
+    }
+
+    errors
+}
+
+/// Bitemporal entities need a primary key: the history table's rows are
+/// matched back to the live row (and to each other, in timeline order) by
+/// that key, and `{table}_as_of` can't reconstruct a row's identity without it.
+pub fn check_history_requires_primary_key(schema: &Schema) -> Vec<TypeCheckError> {
+    let mut errors = Vec::new();
+
+    for entity in &schema.entities {
+        if !entity.is_bitemporal() {
+            continue;
+        }
+        if !entity.fields.values().any(|field| field.primary_key) {
+            errors.push(TypeCheckError::HistoryRequiresPrimaryKey {
+                entity: entity.name.clone(),
+            });
+        }
+    }
+
+    errors
+}
+
+/// `@history` and `@cache` can't coexist on an entity: a cache backend has
+/// no durable log to replay a history table against. `Field.backend` only
+/// ever holds one annotation, so this can't literally happen on a single
+/// field — the closest sound check is across the whole entity.
+pub fn check_history_cache_conflict(schema: &Schema) -> Vec<TypeCheckError> {
+    let mut errors = Vec::new();
+
+    for entity in &schema.entities {
+        let history_field = entity.fields.values()
+            .find(|field| field.backend == Some(BackendAnnotation::History));
+        let cache_field = entity.fields.values()
+            .find(|field| field.backend == Some(BackendAnnotation::Cache));
+
+        if let (Some(history_field), Some(cache_field)) = (history_field, cache_field) {
+            errors.push(TypeCheckError::HistoryCacheConflict {
+                entity: entity.name.clone(),
+                history_field: history_field.name.clone(),
+                cache_field: cache_field.name.clone(),
+            });
+        }
+    }
+
+    errors
+}
+
+/// The dimension range pgvector's ANN index types (HNSW, IVFFlat) support.
+/// A `vector(N)` column itself can go higher, but an `@vector` field is
+/// asking for an index — see `VectorEmitter` in `migration::emitter` — so
+/// it has to stay inside what that index can actually build.
+const MAX_INDEXABLE_VECTOR_DIMENSIONS: usize = 2000;
+
+/// Validates that `@vector` fields have a dimension the ANN index backend
+/// can actually index.
+pub fn check_vector_dimension_range(schema: &Schema) -> Vec<TypeCheckError> {
+    let mut errors = Vec::new();
+
+    for entity in &schema.entities {
+        for field in entity.fields.values() {
+            if field.backend != Some(BackendAnnotation::Vector) {
+                continue;
+            }
+            if let FieldType::Vector(dim) = field.field_type {
+                if dim == 0 || dim > MAX_INDEXABLE_VECTOR_DIMENSIONS {
+                    errors.push(TypeCheckError::VectorDimensionOutOfRange {
+                        entity: entity.name.clone(),
+                        field: field.name.clone(),
+                        dimension: dim,
+                        max: MAX_INDEXABLE_VECTOR_DIMENSIONS,
+                    });
+                }
+            }
+        }
+    }
+
+    errors
+}
+
 /// Validates backend annotation consistency
 pub fn check_annotations(schema: &Schema) -> Vec<TypeCheckError> {
     let mut errors = Vec::new();
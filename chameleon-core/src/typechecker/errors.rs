@@ -1,21 +1,32 @@
 use thiserror::Error;
 
+/// How seriously a `TypeCheckError` should be taken: an `Error` makes a
+/// schema invalid, a `Warning` flags something worth knowing about (e.g. a
+/// table that can't be upserted into) without blocking it.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum Severity {
+    Error,
+    Warning,
+}
+
 #[derive(Error, Debug, Clone, PartialEq)]
 pub enum TypeCheckError {
     // Relaciones
-    #[error("Entity '{entity}' references unknown entity '{target}' in relation '{relation}'")]
+    #[error("Entity '{entity}' references unknown entity '{target}' in relation '{relation}'{}", .suggestion.as_ref().map(|s| format!(" (did you mean '{}'?)", s)).unwrap_or_default())]
     UnknownRelationTarget {
         entity: String,
         relation: String,
         target: String,
+        suggestion: Option<String>,
     },
 
-    #[error("Relation '{relation}' in '{entity}' references foreign key '{foreign_key}', but '{target}' has no such field")]
+    #[error("Relation '{relation}' in '{entity}' references foreign key '{foreign_key}', but '{target}' has no such field{}", .suggestion.as_ref().map(|s| format!(" (did you mean '{}'?)", s)).unwrap_or_default())]
     InvalidForeignKey {
         entity: String,
         relation: String,
         target: String,
         foreign_key: String,
+        suggestion: Option<String>,
     },
 
     #[error("HasMany relation '{relation}' in '{entity}' requires a 'via' foreign key")]
@@ -72,6 +83,128 @@ pub enum TypeCheckError {
     DuplicateField {
         entity: String,
         field: String,
-    }
+    },
+
+    // Many-to-many
+    #[error("ManyToMany relation '{relation}' in '{entity}' names through table '{through}', which collides with an existing entity")]
+    ThroughTableCollidesWithEntity {
+        entity: String,
+        relation: String,
+        through: String,
+    },
+
+    #[error("ManyToMany relation '{relation}' in '{entity}' declares through table '{through}', but no relation on the other side reciprocates it")]
+    AsymmetricManyToMany {
+        entity: String,
+        relation: String,
+        through: String,
+    },
+
+    // Namespaces
+    #[error("Relation '{relation}' in '{entity}' references '{target}', which matches entities in more than one namespace: {candidates:?}. Qualify it as 'namespace.{target}'")]
+    AmbiguousEntityReference {
+        entity: String,
+        relation: String,
+        target: String,
+        candidates: Vec<String>,
+    },
+
+    // Bitemporal
+    #[error("Entity '{entity}' is bitemporal (@temporal or has an @history field) and cannot declare its own '{field}' column; that name is reserved for the generated validity range")]
+    TemporalColumnReserved {
+        entity: String,
+        field: String,
+    },
+
+    // Query layer
+    #[error("Query pattern references unknown entity '{entity}'")]
+    UnknownQueryEntity {
+        entity: String,
+    },
+
+    #[error("Query pattern references unknown field '{field}' on entity '{entity}'{}", .suggestion.as_ref().map(|s| format!(" (did you mean '{}'?)", s)).unwrap_or_default())]
+    UnknownQueryField {
+        entity: String,
+        field: String,
+        suggestion: Option<String>,
+    },
 
+    #[error("Query variable '{var}' is bound to entity '{first_entity}' and also to '{second_entity}', which can't both be true of the same row")]
+    QueryEntityVarConflict {
+        var: String,
+        first_entity: String,
+        second_entity: String,
+    },
+
+    #[error("`or` branch binds query variable '{var}' to entity '{entity}', but every branch must operate only on variables already bound outside the `or`")]
+    OrBranchIntroducesEntityVar {
+        var: String,
+        entity: String,
+    },
+
+    #[error("Query variable '{var}' can't satisfy all its bindings: its possible types have no value in common")]
+    QueryTypeConflict {
+        var: String,
+    },
+
+    #[error("Predicate '{op}' on query variable '{var}' requires a numeric or timestamp type, but it's constrained to {allowed:?}")]
+    QueryPredicateTypeMismatch {
+        op: String,
+        var: String,
+        allowed: Vec<String>,
+    },
+
+    #[error("`ground` clause expected {expected} value(s) per row, found {found}")]
+    GroundArityMismatch {
+        expected: usize,
+        found: usize,
+    },
+
+    #[error("`ground` clause binds query variable '{var}' to values of more than one type")]
+    GroundValuesNotUniform {
+        var: String,
+    },
+
+    // Upserts
+    #[error("Entity '{entity}' has no primary key or unique field, so it has no sound ON CONFLICT target")]
+    NonUpsertableEntity {
+        entity: String,
+    },
+
+    #[error("Entity '{entity}' is bitemporal (@temporal or has an @history field) but has no primary key, so its history rows can't be matched back to a live row")]
+    HistoryRequiresPrimaryKey {
+        entity: String,
+    },
+
+    // `backend` is a single `Option<BackendAnnotation>` per field, so two
+    // annotations can never collide on the *same* field; the closest
+    // meaningful version of this rule is entity-wide: an @history field and
+    // an @cache field can't coexist, because the cache backend has nothing
+    // to replay a history table against.
+    #[error("Entity '{entity}' mixes @history field '{history_field}' with @cache field '{cache_field}'; a cached entity can't maintain a replay history")]
+    HistoryCacheConflict {
+        entity: String,
+        history_field: String,
+        cache_field: String,
+    },
+
+    // Vector indexes
+    #[error("Field '{field}' in '{entity}' is @vector with dimension {dimension}, outside the 1..={max} range the ANN index backend supports")]
+    VectorDimensionOutOfRange {
+        entity: String,
+        field: String,
+        dimension: usize,
+        max: usize,
+    },
+}
+
+impl TypeCheckError {
+    /// Whether this finding should block a schema as invalid, or just be
+    /// surfaced alongside it.
+    pub fn severity(&self) -> Severity {
+        match self {
+            TypeCheckError::NonUpsertableEntity { .. } => Severity::Warning,
+            _ => Severity::Error,
+        }
+    }
 }
\ No newline at end of file
@@ -3,7 +3,7 @@ mod relations;
 mod constraints;
 
 use crate::ast::Schema;
-use errors::TypeCheckError;
+use errors::{Severity, TypeCheckError};
 use std::collections::HashMap;
 
 /// Result of type checking a schema
@@ -13,20 +13,36 @@ pub struct TypeCheckResult {
 }
 
 impl TypeCheckResult {
-    /// Returns true if the schema passed all checks
+    /// Returns true if the schema has no `Severity::Error` findings.
+    /// Warnings (e.g. a non-upsertable entity) don't affect validity.
     pub fn is_valid(&self) -> bool {
-        self.errors.is_empty()
+        !self.errors.iter().any(|e| e.severity() == Severity::Error)
     }
 
     /// Returns a formatted error report
     pub fn error_report(&self) -> String {
-        if self.is_valid() {
+        let (errors, warnings): (Vec<&TypeCheckError>, Vec<&TypeCheckError>) = self.errors.iter()
+            .partition(|e| e.severity() == Severity::Error);
+
+        if errors.is_empty() && warnings.is_empty() {
             return "✅ Schema is valid".to_string();
         }
 
-        let mut report = format!("❌ Found {} error(s):\n\n", self.errors.len());
-        for (i, error) in self.errors.iter().enumerate() {
-            report.push_str(&format!("  {}. {}\n", i + 1, error));
+        let mut report = String::new();
+        if !errors.is_empty() {
+            report.push_str(&format!("❌ Found {} error(s):\n\n", errors.len()));
+            for (i, error) in errors.iter().enumerate() {
+                report.push_str(&format!("  {}. {}\n", i + 1, error));
+            }
+        }
+        if !warnings.is_empty() {
+            if !errors.is_empty() {
+                report.push('\n');
+            }
+            report.push_str(&format!("⚠️  Found {} warning(s):\n\n", warnings.len()));
+            for (i, warning) in warnings.iter().enumerate() {
+                report.push_str(&format!("  {}. {}\n", i + 1, warning));
+            }
         }
         report
     }
@@ -41,7 +57,7 @@ pub fn type_check(schema: &Schema) -> TypeCheckResult {
     // Rastrear índices de aparición
     for (i, entity) in schema.entities.iter().enumerate() {
         entity_names
-            .entry(entity.name.clone())
+            .entry(entity.qualified_name())
             .or_insert_with(Vec::new)
             .push(i);
     }
@@ -90,6 +106,11 @@ pub fn type_check(schema: &Schema) -> TypeCheckResult {
     // Constraints
     errors.extend(constraints::check_primary_keys(schema));
     errors.extend(constraints::check_annotations(schema));
+    errors.extend(constraints::check_temporal_columns(schema));
+    errors.extend(constraints::check_upsertability(schema));
+    errors.extend(constraints::check_history_requires_primary_key(schema));
+    errors.extend(constraints::check_history_cache_conflict(schema));
+    errors.extend(constraints::check_vector_dimension_range(schema));
 
     TypeCheckResult { errors }
 }
@@ -198,6 +219,29 @@ mod tests {
         assert!(result.errors.iter().any(|e| matches!(e, TypeCheckError::UnknownRelationTarget { .. })));
     }
 
+    #[test]
+    fn test_unknown_relation_target_suggests_closest_entity_name() {
+        let schema = build_schema(vec![
+            ("User",
+                vec![("id", FieldType::UUID, true, false, None)],
+                vec![("orders", RelationKind::HasMany, "Orde", Some("user_id"))]),
+            ("Order",
+                vec![("id", FieldType::UUID, true, false, None)],
+                vec![]),
+        ]);
+
+        let result = type_check(&schema);
+        let error = result.errors.iter()
+            .find(|e| matches!(e, TypeCheckError::UnknownRelationTarget { .. }))
+            .unwrap();
+        match error {
+            TypeCheckError::UnknownRelationTarget { suggestion, .. } => {
+                assert_eq!(suggestion.as_deref(), Some("Order"));
+            }
+            _ => unreachable!(),
+        }
+    }
+
     #[test]
     fn test_invalid_foreign_key() {
         let schema = build_schema(vec![
@@ -231,6 +275,101 @@ mod tests {
         assert!(result.errors.iter().any(|e| matches!(e, TypeCheckError::MissingForeignKey { .. })));
     }
 
+    #[test]
+    fn test_one_sided_many_to_many_is_rejected() {
+        let mut schema = Schema::new();
+
+        let mut post = Entity::new("Post".to_string());
+        post.add_field(Field {
+            name: "id".to_string(), field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        post.add_relation(Relation {
+            name: "tags".to_string(),
+            kind: RelationKind::ManyToMany,
+            target_entity: "Tag".to_string(),
+            foreign_key: None,
+            through: Some("post_tags".to_string()),
+        });
+        schema.add_entity(post);
+
+        // Tag declares no relation back to Post at all.
+        let mut tag = Entity::new("Tag".to_string());
+        tag.add_field(Field {
+            name: "id".to_string(), field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        schema.add_entity(tag);
+
+        let result = type_check(&schema);
+        assert!(!result.is_valid());
+        assert!(result.errors.iter().any(|e| matches!(e, TypeCheckError::AsymmetricManyToMany { .. })));
+    }
+
+    // ─── NAMESPACES ───
+
+    #[test]
+    fn test_qualified_relation_target_resolves() {
+        let mut schema = Schema::new();
+
+        let mut user = Entity::new("User".to_string());
+        user.add_field(Field {
+            name: "id".to_string(), field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        user.add_relation(Relation {
+            name: "orders".to_string(),
+            kind: RelationKind::HasMany,
+            target_entity: "analytics.Order".to_string(),
+            foreign_key: Some("user_id".to_string()),
+            through: None,
+        });
+        schema.add_entity(user);
+
+        let mut order = Entity::from_qualified_name("analytics.Order");
+        order.add_field(Field {
+            name: "id".to_string(), field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        order.add_field(Field {
+            name: "user_id".to_string(), field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: false,
+            default: None, backend: None,
+        });
+        schema.add_entity(order);
+
+        let result = type_check(&schema);
+        assert!(!result.errors.iter().any(|e| matches!(e, TypeCheckError::UnknownRelationTarget { .. })), "{}", result.error_report());
+    }
+
+    #[test]
+    fn test_same_bare_name_in_different_namespaces_is_not_a_duplicate() {
+        let mut schema = Schema::new();
+
+        let mut staging_order = Entity::from_qualified_name("staging.Order");
+        staging_order.add_field(Field {
+            name: "id".to_string(), field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        schema.add_entity(staging_order);
+
+        let mut reporting_order = Entity::from_qualified_name("reporting.Order");
+        reporting_order.add_field(Field {
+            name: "id".to_string(), field_type: FieldType::UUID,
+            nullable: false, unique: false, primary_key: true,
+            default: None, backend: None,
+        });
+        schema.add_entity(reporting_order);
+
+        let result = type_check(&schema);
+        assert!(!result.errors.iter().any(|e| matches!(e, TypeCheckError::DuplicateEntity { .. })), "{}", result.error_report());
+    }
+
     // ─── PRIMARY KEY ERRORS ───
 
     #[test]
@@ -261,6 +400,101 @@ mod tests {
         assert!(result.errors.iter().any(|e| matches!(e, TypeCheckError::MultiplePrimaryKeys { .. })));
     }
 
+    // ─── UPSERTABILITY WARNING ───
+
+    #[test]
+    fn test_entity_without_pk_or_unique_is_flagged_non_upsertable() {
+        let schema = build_schema(vec![
+            ("Event",
+                vec![("payload", FieldType::String, false, false, None)],
+                vec![]),
+        ]);
+
+        let result = type_check(&schema);
+        assert!(result.errors.iter().any(|e| matches!(e, TypeCheckError::NonUpsertableEntity { .. })));
+    }
+
+    #[test]
+    fn test_non_upsertable_warning_does_not_invalidate_schema() {
+        let result = TypeCheckResult {
+            errors: vec![TypeCheckError::NonUpsertableEntity { entity: "Event".to_string() }],
+        };
+
+        assert!(result.is_valid());
+        assert!(result.error_report().contains("warning"));
+    }
+
+    // ─── BITEMPORAL / @history ───
+
+    #[test]
+    fn test_history_field_without_primary_key_is_rejected() {
+        let schema = build_schema(vec![
+            ("AuditLog",
+                vec![("note", FieldType::String, false, false, Some(BackendAnnotation::History))],
+                vec![]),
+        ]);
+
+        let result = type_check(&schema);
+        assert!(result.errors.iter().any(|e| matches!(e, TypeCheckError::HistoryRequiresPrimaryKey { .. })));
+    }
+
+    #[test]
+    fn test_history_annotation_alone_makes_entity_bitemporal() {
+        let schema = build_schema(vec![
+            ("AuditLog",
+                vec![("id", FieldType::UUID, true, false, None),
+                     ("note", FieldType::String, false, false, Some(BackendAnnotation::History))],
+                vec![]),
+        ]);
+
+        assert!(schema.get_entity("AuditLog").unwrap().is_bitemporal());
+        let result = type_check(&schema);
+        assert!(!result.errors.iter().any(|e| matches!(e, TypeCheckError::HistoryRequiresPrimaryKey { .. })));
+    }
+
+    #[test]
+    fn test_history_and_cache_fields_on_same_entity_conflict() {
+        let schema = build_schema(vec![
+            ("Product",
+                vec![("id", FieldType::UUID, true, false, None),
+                     ("note", FieldType::String, false, false, Some(BackendAnnotation::History)),
+                     ("views", FieldType::Int, false, false, Some(BackendAnnotation::Cache))],
+                vec![]),
+        ]);
+
+        let result = type_check(&schema);
+        assert!(result.errors.iter().any(|e| matches!(e, TypeCheckError::HistoryCacheConflict { .. })));
+    }
+
+    // ─── VECTOR INDEX DIMENSION ───
+
+    #[test]
+    fn test_vector_field_within_range_is_accepted() {
+        let schema = build_schema(vec![
+            ("Product",
+                vec![("id", FieldType::UUID, true, false, None),
+                     ("embedding", FieldType::Vector(384), false, false, Some(BackendAnnotation::Vector))],
+                vec![]),
+        ]);
+
+        let result = type_check(&schema);
+        assert!(!result.errors.iter().any(|e| matches!(e, TypeCheckError::VectorDimensionOutOfRange { .. })));
+    }
+
+    #[test]
+    fn test_vector_field_beyond_index_range_is_rejected() {
+        let schema = build_schema(vec![
+            ("Product",
+                vec![("id", FieldType::UUID, true, false, None),
+                     ("embedding", FieldType::Vector(4096), false, false, Some(BackendAnnotation::Vector))],
+                vec![]),
+        ]);
+
+        let result = type_check(&schema);
+        assert!(!result.is_valid());
+        assert!(result.errors.iter().any(|e| matches!(e, TypeCheckError::VectorDimensionOutOfRange { .. })));
+    }
+
     // ─── ANNOTATION ERRORS ───
 
     #[test]
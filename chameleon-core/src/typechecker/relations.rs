@@ -1,21 +1,48 @@
 use crate::ast::{Schema, RelationKind};
+use crate::migration::generator::target_matches;
+use crate::suggest::suggest;
 use super::errors::TypeCheckError;
 
 /// Validates all relations in the schema
 pub fn check_relations(schema: &Schema) -> Vec<TypeCheckError> {
     let mut errors = Vec::new();
 
-    for (entity_name, entity) in &schema.entities {
+    for entity in &schema.entities {
+        let entity_name = &entity.name;
         for (_, relation) in &entity.relations {
+            // 0. A bare (unqualified) target must resolve to exactly one
+            // namespace, or the caller needs to write `namespace.Entity`.
+            if !relation.target_entity.contains('.') {
+                let candidates: Vec<String> = schema.entities.iter()
+                    .filter(|e| e.name == relation.target_entity)
+                    .map(|e| e.qualified_name())
+                    .collect();
+                if candidates.len() > 1 {
+                    errors.push(TypeCheckError::AmbiguousEntityReference {
+                        entity: entity_name.clone(),
+                        relation: relation.name.clone(),
+                        target: relation.target_entity.clone(),
+                        candidates,
+                    });
+                    continue;
+                }
+            }
+
             // 1. Target entity exists
-            if !schema.entities.contains_key(&relation.target_entity) {
+            let Some(target) = schema.entities.iter().find(|e| target_matches(e, &relation.target_entity)) else {
+                let suggestion = suggest(
+                    &relation.target_entity,
+                    schema.entities.iter().map(|e| e.name.as_str()),
+                ).map(|s| s.to_string());
+
                 errors.push(TypeCheckError::UnknownRelationTarget {
                     entity: entity_name.clone(),
                     relation: relation.name.clone(),
                     target: relation.target_entity.clone(),
+                    suggestion,
                 });
                 continue; // No tiene sentido validar más si el target no existe
-            }
+            };
 
             // 2. HasMany requiere foreign key
             if relation.kind == RelationKind::HasMany && relation.foreign_key.is_none() {
@@ -28,14 +55,46 @@ pub fn check_relations(schema: &Schema) -> Vec<TypeCheckError> {
 
             // 3. Foreign key existe en la entidad target
             if let Some(fk) = &relation.foreign_key {
-                let target = schema.entities.get(&relation.target_entity).unwrap();
                 if !target.fields.contains_key(fk) {
+                    let suggestion = suggest(fk, target.fields.keys().map(|s| s.as_str()))
+                        .map(|s| s.to_string());
+
                     errors.push(TypeCheckError::InvalidForeignKey {
                         entity: entity_name.clone(),
                         relation: relation.name.clone(),
                         target: relation.target_entity.clone(),
                         foreign_key: fk.clone(),
+                        suggestion,
+                    });
+                }
+            }
+
+            // 4. Una tabla `through` no puede nombrar una entidad existente
+            if relation.kind == RelationKind::ManyToMany {
+                if let Some(through) = &relation.through {
+                    if schema.entities.iter().any(|e| e.name == *through) {
+                        errors.push(TypeCheckError::ThroughTableCollidesWithEntity {
+                            entity: entity_name.clone(),
+                            relation: relation.name.clone(),
+                            through: through.clone(),
+                        });
+                    }
+
+                    // 5. Both sides of a ManyToMany must declare the same
+                    // through table back at each other — otherwise one
+                    // direction's join table wouldn't match the other's.
+                    let reciprocated = target.relations.values().any(|r| {
+                        r.kind == RelationKind::ManyToMany
+                            && r.through.as_deref() == Some(through.as_str())
+                            && (r.target_entity == entity.name || r.target_entity == entity.qualified_name())
                     });
+                    if !reciprocated {
+                        errors.push(TypeCheckError::AsymmetricManyToMany {
+                            entity: entity_name.clone(),
+                            relation: relation.name.clone(),
+                            through: through.clone(),
+                        });
+                    }
                 }
             }
         }
@@ -50,7 +109,8 @@ pub fn check_circular_dependencies(schema: &Schema) -> Vec<TypeCheckError> {
     let mut visited: Vec<String> = Vec::new();
     let mut in_stack: Vec<String> = Vec::new();
 
-    for entity_name in schema.entities.keys() {
+    for entity in &schema.entities {
+        let entity_name = &entity.name;
         if !visited.contains(entity_name) {
             if let Some(cycle) = dfs(schema, entity_name, &mut visited, &mut in_stack) {
                 errors.push(TypeCheckError::CircularDependency { cycle });
@@ -70,7 +130,7 @@ fn dfs(
     visited.push(current.to_string());
     in_stack.push(current.to_string());
 
-    if let Some(entity) = schema.entities.get(current) {
+    if let Some(entity) = schema.get_entity(current) {
         for (_, relation) in &entity.relations {
             // BelongsTo is just the inverse side of a relation, skip it
             if relation.kind == RelationKind::BelongsTo {